@@ -0,0 +1,50 @@
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{Action, ActionResult, State};
+
+/// Future that completes an action with the output of the inner future.
+///
+/// Created by [`Action::run_async`].
+pub struct ActionFuture<F: Future<Output: ActionResult>> {
+    action: Option<Action>,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future<Output: ActionResult>> ActionFuture<F> {
+    pub(crate) fn new(action: Action, future: F) -> Self {
+        Self {
+            action: Some(action),
+            future: Box::pin(future),
+        }
+    }
+}
+
+impl<F: Future<Output: ActionResult>> Future for ActionFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        match this.future.as_mut().poll(cx) {
+            Poll::Ready(output) => match this.action.take() {
+                Some(action) => Poll::Ready(action.complete_with(output)),
+                None => Poll::Ready(output),
+            },
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<F: Future<Output: ActionResult>> Drop for ActionFuture<F> {
+    /// The action is cancelled if the future is dropped before it completes, e.g., by a timeout.
+    fn drop(&mut self) {
+        if let Some(mut action) = self.action.take()
+            && action.state == State::InProgress
+        {
+            action.cancel();
+        }
+    }
+}
@@ -0,0 +1,972 @@
+mod future;
+mod multi;
+mod progress;
+mod spinner;
+
+use core::{
+    error::Error,
+    fmt::Display,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    time::Duration,
+};
+use std::{
+    sync::Arc,
+    time::{Instant, SystemTime},
+};
+
+use ts_rust_helper::style::*;
+
+use crate::{
+    output::{self, JsonLine, OutputFormat, Stream, Verbosity},
+    session::{self, ActionRecord},
+};
+
+pub use future::ActionFuture;
+pub use multi::MultiAction;
+
+use progress::Progress;
+use spinner::Spinner;
+
+/// Extension trait to update an action state.
+pub trait ActionResult {
+    /// Use self to update the action.
+    fn bind_result(self, action: Action) -> Self;
+
+    /// Use self to error the action.
+    fn bind_error(self, action: &mut Action) -> Self;
+}
+
+/// The error is not recorded, use [`ActionMessageResult`] or [`ActionErrorResult`] to record it.
+impl<T, E> ActionResult for Result<T, E> {
+    fn bind_result(self, mut action: Action) -> Self {
+        match &self {
+            Ok(_) => action.set_state(State::Success),
+            Err(_) => action.set_state(State::Error),
+        }
+
+        self
+    }
+
+    fn bind_error(self, action: &mut Action) -> Self {
+        if self.is_err() {
+            action.set_state(State::Error)
+        }
+        self
+    }
+}
+impl<T> ActionResult for Option<T> {
+    fn bind_result(self, mut action: Action) -> Self {
+        match &self {
+            Some(_) => action.set_state(State::Success),
+            None => action.set_state(State::Error),
+        }
+
+        self
+    }
+
+    fn bind_error(self, action: &mut Action) -> Self {
+        if self.is_none() {
+            action.set_state(State::Error)
+        }
+        self
+    }
+}
+
+/// Extension trait to update an action state with a failure state other than [`State::Error`].
+pub trait ActionFailureResult {
+    /// Use self to update the action, completing it with `failure` instead of [`State::Error`],
+    /// e.g., [`State::Warning`] for non-fatal failures or [`State::Cancelled`].
+    fn bind_result_or(self, action: Action, failure: State) -> Self;
+}
+
+impl<T, E> ActionFailureResult for Result<T, E> {
+    fn bind_result_or(self, mut action: Action, failure: State) -> Self {
+        match &self {
+            Ok(_) => action.set_state(State::Success),
+            Err(_) => action.set_state(failure),
+        }
+
+        self
+    }
+}
+impl<T> ActionFailureResult for Option<T> {
+    fn bind_result_or(self, mut action: Action, failure: State) -> Self {
+        match &self {
+            Some(_) => action.set_state(State::Success),
+            None => action.set_state(failure),
+        }
+
+        self
+    }
+}
+
+/// Extension trait to update an action state where `Ok(None)` means the action was skipped.
+pub trait ActionSkipResult {
+    /// Use self to update the action, skipping it if there was nothing to do.
+    fn bind_skippable(self, action: Action) -> Self;
+}
+
+impl<T, E> ActionSkipResult for Result<Option<T>, E> {
+    fn bind_skippable(self, mut action: Action) -> Self {
+        match &self {
+            Ok(Some(_)) => action.set_state(State::Success),
+            Ok(None) => action.set_state(State::Skipped),
+            Err(_) => action.set_state(State::Error),
+        }
+
+        self
+    }
+}
+
+/// Extension trait to update an action state, recording the error message so the failure is
+/// explained, for errors that only implement [`Display`], e.g., `String`.
+pub trait ActionMessageResult {
+    /// Use self to update the action, recording the error message if there is one.
+    fn bind_result_message(self, action: Action) -> Self;
+
+    /// Use self to error the action, recording the error message if there is one.
+    fn bind_error_message(self, action: &mut Action) -> Self;
+}
+
+impl<T, E: Display> ActionMessageResult for Result<T, E> {
+    fn bind_result_message(self, mut action: Action) -> Self {
+        match &self {
+            Ok(_) => action.set_state(State::Success),
+            Err(error) => action.set_error_message(error),
+        }
+
+        self
+    }
+
+    fn bind_error_message(self, action: &mut Action) -> Self {
+        if let Err(error) = &self {
+            action.set_error_message(error);
+        }
+        self
+    }
+}
+
+/// Extension trait to update an action state, recording the error and its sources so the failure
+/// is explained.
+pub trait ActionErrorResult {
+    /// Use self to update the action, recording the error if there is one.
+    fn bind_result_detailed(self, action: Action) -> Self;
+
+    /// Use self to error the action, recording the error if there is one.
+    fn bind_error_detailed(self, action: &mut Action) -> Self;
+}
+
+impl<T, E: Error> ActionErrorResult for Result<T, E> {
+    fn bind_result_detailed(self, mut action: Action) -> Self {
+        match &self {
+            Ok(_) => action.set_state(State::Success),
+            Err(error) => action.set_error(error),
+        }
+
+        self
+    }
+
+    fn bind_error_detailed(self, action: &mut Action) -> Self {
+        if let Err(error) = &self {
+            action.set_error(error);
+        }
+        self
+    }
+}
+
+/// How much of a recorded error is shown when an action errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ErrorDetail {
+    /// The error is not shown.
+    #[default]
+    Hidden,
+    /// The error message is shown after the failed action.
+    Message,
+    /// The error message is shown after the failed action, and each source is shown on the
+    /// indented lines below it.
+    Chain,
+}
+
+/// Options for actions created by [`Action::run`] and the other runners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct RunOptions {
+    /// Show the elapsed time when the action completes if it took at least this long.
+    pub elapsed_threshold: Option<Duration>,
+    /// How much of a recorded error is shown when the action errors.
+    pub error_detail: ErrorDetail,
+}
+
+impl RunOptions {
+    /// Show the elapsed time when the action completes if it took at least `threshold`.
+    pub fn with_elapsed(mut self, threshold: Duration) -> Self {
+        self.elapsed_threshold = Some(threshold);
+        self
+    }
+
+    /// Never show the elapsed time.
+    pub fn without_elapsed(mut self) -> Self {
+        self.elapsed_threshold = None;
+        self
+    }
+
+    /// Set how much of a recorded error is shown when the action errors.
+    pub fn with_error_detail(mut self, detail: ErrorDetail) -> Self {
+        self.error_detail = detail;
+        self
+    }
+
+    fn apply(self, action: &mut Action) {
+        action.elapsed_threshold = self.elapsed_threshold;
+        action.error_detail = self.error_detail;
+    }
+}
+
+impl Default for RunOptions {
+    /// Show the elapsed time for actions taking at least a second, and the error message.
+    fn default() -> Self {
+        Self {
+            elapsed_threshold: Some(Duration::from_secs(1)),
+            error_detail: ErrorDetail::Message,
+        }
+    }
+}
+
+/// Action State
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    /// The action is in progress.
+    InProgress,
+    /// The action was a success.
+    Success,
+    /// The action completed with warnings.
+    Warning,
+    /// The action was an error.
+    Error,
+    /// The action was skipped, e.g., because the output is up to date.
+    Skipped,
+    /// The action was cancelled before it completed.
+    Cancelled,
+}
+
+impl State {
+    /// The name of the state in machine readable output.
+    fn name(self) -> &'static str {
+        match self {
+            Self::InProgress => "in_progress",
+            Self::Success => "success",
+            Self::Warning => "warning",
+            Self::Error => "error",
+            Self::Skipped => "skipped",
+            Self::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// The outcomes of child actions that affect their parent.
+#[derive(Default)]
+struct ChildOutcomes {
+    failed: AtomicBool,
+    warned: AtomicBool,
+}
+
+/// The additional indent for child actions.
+const CHILD_INDENT: usize = 2;
+
+/// Source of unique action identifiers, used to track which action printed the last line.
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Get a new unique identifier for an owner of output lines.
+pub(crate) fn next_id() -> u64 {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Action progress reporter.
+pub struct Action {
+    /// The current state of the action
+    pub state: State,
+    /// Verb for the in progress action.
+    pub actioning_verb: String,
+    /// Verb for the completed action.
+    pub actioned_verb: String,
+    /// Details for the action.
+    pub detail: String,
+    /// The number of characters to indent the action by.
+    pub indent: usize,
+    /// Should the action overwrite the previous line when printing. The line is only overwritten if
+    /// it was printed by this action and the output supports cursor control, otherwise each state
+    /// is printed on its own line.
+    pub should_overwrite: bool,
+    /// Show the elapsed time when the action completes if it took at least this long.
+    pub elapsed_threshold: Option<Duration>,
+    /// The state an unfinished action is set to when dropped, e.g., when returning early with `?`
+    /// or panicking. [`State::InProgress`] leaves the action unfinished.
+    pub drop_state: State,
+    /// How much of a recorded error is shown when the action errors.
+    pub error_detail: ErrorDetail,
+
+    id: u64,
+    parent_id: Option<u64>,
+    started: SystemTime,
+    start: Instant,
+    /// When the action left [`State::InProgress`].
+    finish: Option<Instant>,
+    spinner: Option<Spinner>,
+    progress: Option<Progress>,
+    /// The multi-action display this action is drawn in.
+    multi: Option<MultiAction>,
+    /// The recorded error message followed by the messages of its sources.
+    error: Vec<String>,
+    /// Set if this action has warnings.
+    warned: bool,
+    /// Set by child actions when they complete.
+    children: Arc<ChildOutcomes>,
+    /// The parent's `children` outcomes, if this is a child action.
+    parent: Option<Arc<ChildOutcomes>>,
+}
+
+impl Action {
+    /// Create and report a new in progress action.
+    pub fn new<S1: ToString, S2: ToString, S3: ToString>(
+        actioning_verb: S1,
+        actioned_verb: S2,
+        detail: S3,
+        indent: usize,
+    ) -> Self {
+        let mut progress = Self::unreported(
+            actioning_verb.to_string(),
+            actioned_verb.to_string(),
+            detail.to_string(),
+            indent,
+        );
+        progress.print();
+        progress
+    }
+
+    /// Create and report a new in progress child action, indented below this action.
+    ///
+    /// If the child errors or has warnings, this action will error or have warnings when it
+    /// completes.
+    pub fn child<S1: ToString, S2: ToString, S3: ToString>(
+        &self,
+        actioning_verb: S1,
+        actioned_verb: S2,
+        detail: S3,
+    ) -> Self {
+        let mut child = self.unreported_child(
+            actioning_verb.to_string(),
+            actioned_verb.to_string(),
+            detail.to_string(),
+        );
+        child.print();
+        child
+    }
+
+    /// Create and report a new in progress action with determinate progress towards a total.
+    pub fn progress<S1: ToString, S2: ToString, S3: ToString>(
+        actioning_verb: S1,
+        actioned_verb: S2,
+        detail: S3,
+        indent: usize,
+        total: u64,
+    ) -> Self {
+        let mut progress = Self::unreported(
+            actioning_verb.to_string(),
+            actioned_verb.to_string(),
+            detail.to_string(),
+            indent,
+        );
+        progress.progress = Some(Progress::new(total));
+        progress.print();
+        progress
+    }
+
+    /// Create and report a new in progress child action with determinate progress towards a total.
+    pub fn child_progress<S1: ToString, S2: ToString, S3: ToString>(
+        &self,
+        actioning_verb: S1,
+        actioned_verb: S2,
+        detail: S3,
+        total: u64,
+    ) -> Self {
+        let mut child = self.unreported_child(
+            actioning_verb.to_string(),
+            actioned_verb.to_string(),
+            detail.to_string(),
+        );
+        child.progress = Some(Progress::new(total));
+        child.print();
+        child
+    }
+
+    /// Run a closure as an action, completing the action from the returned [`Result`] or
+    /// [`Option`] with the default [`RunOptions`].
+    ///
+    /// The closure is given the action to create child actions or mark warnings.
+    pub fn run<S1, S2, S3, R, F>(
+        actioning_verb: S1,
+        actioned_verb: S2,
+        detail: S3,
+        indent: usize,
+        f: F,
+    ) -> R
+    where
+        S1: ToString,
+        S2: ToString,
+        S3: ToString,
+        R: ActionResult,
+        F: FnOnce(&mut Self) -> R,
+    {
+        Self::run_with(
+            actioning_verb,
+            actioned_verb,
+            detail,
+            indent,
+            RunOptions::default(),
+            f,
+        )
+    }
+
+    /// Run a closure as an action with some options, see [`Action::run`].
+    ///
+    /// If the closure already completed the action, the state is kept. To explain a failure,
+    /// record the error in the closure, e.g., with [`ActionMessageResult::bind_error_message`] or
+    /// [`ActionErrorResult::bind_error_detailed`].
+    pub fn run_with<S1, S2, S3, R, F>(
+        actioning_verb: S1,
+        actioned_verb: S2,
+        detail: S3,
+        indent: usize,
+        options: RunOptions,
+        f: F,
+    ) -> R
+    where
+        S1: ToString,
+        S2: ToString,
+        S3: ToString,
+        R: ActionResult,
+        F: FnOnce(&mut Self) -> R,
+    {
+        let mut action = Self::unreported(
+            actioning_verb.to_string(),
+            actioned_verb.to_string(),
+            detail.to_string(),
+            indent,
+        );
+        options.apply(&mut action);
+        action.print();
+
+        let result = f(&mut action);
+        action.complete_with(result)
+    }
+
+    /// Run a closure as a child action, completing the child from the returned [`Result`] or
+    /// [`Option`].
+    ///
+    /// The child inherits the elapsed threshold and error detail of this action.
+    pub fn run_child<S1, S2, S3, R, F>(
+        &self,
+        actioning_verb: S1,
+        actioned_verb: S2,
+        detail: S3,
+        f: F,
+    ) -> R
+    where
+        S1: ToString,
+        S2: ToString,
+        S3: ToString,
+        R: ActionResult,
+        F: FnOnce(&mut Self) -> R,
+    {
+        let options = RunOptions {
+            elapsed_threshold: self.elapsed_threshold,
+            error_detail: self.error_detail,
+        };
+        self.run_child_with(actioning_verb, actioned_verb, detail, options, f)
+    }
+
+    /// Run a closure as a child action with some options, see [`Action::run_child`].
+    pub fn run_child_with<S1, S2, S3, R, F>(
+        &self,
+        actioning_verb: S1,
+        actioned_verb: S2,
+        detail: S3,
+        options: RunOptions,
+        f: F,
+    ) -> R
+    where
+        S1: ToString,
+        S2: ToString,
+        S3: ToString,
+        R: ActionResult,
+        F: FnOnce(&mut Self) -> R,
+    {
+        let mut child = self.unreported_child(
+            actioning_verb.to_string(),
+            actioned_verb.to_string(),
+            detail.to_string(),
+        );
+        options.apply(&mut child);
+        child.print();
+
+        let result = f(&mut child);
+        child.complete_with(result)
+    }
+
+    /// Run a future as an action, completing the action from the output [`Result`] or
+    /// [`Option`] with the default [`RunOptions`].
+    ///
+    /// The action is reported when this is called, and completed when the future completes. If
+    /// the future is dropped before it completes, the action is cancelled.
+    pub fn run_async<S1, S2, S3, F>(
+        actioning_verb: S1,
+        actioned_verb: S2,
+        detail: S3,
+        indent: usize,
+        future: F,
+    ) -> ActionFuture<F>
+    where
+        S1: ToString,
+        S2: ToString,
+        S3: ToString,
+        F: Future<Output: ActionResult>,
+    {
+        Self::run_async_with(
+            actioning_verb,
+            actioned_verb,
+            detail,
+            indent,
+            RunOptions::default(),
+            future,
+        )
+    }
+
+    /// Run a future as an action with some options, see [`Action::run_async`].
+    pub fn run_async_with<S1, S2, S3, F>(
+        actioning_verb: S1,
+        actioned_verb: S2,
+        detail: S3,
+        indent: usize,
+        options: RunOptions,
+        future: F,
+    ) -> ActionFuture<F>
+    where
+        S1: ToString,
+        S2: ToString,
+        S3: ToString,
+        F: Future<Output: ActionResult>,
+    {
+        let mut action = Self::unreported(
+            actioning_verb.to_string(),
+            actioned_verb.to_string(),
+            detail.to_string(),
+            indent,
+        );
+        options.apply(&mut action);
+        action.print();
+
+        ActionFuture::new(action, future)
+    }
+
+    /// Complete the action from a result, unless it was already completed.
+    pub(crate) fn complete_with<R: ActionResult>(self, result: R) -> R {
+        if self.state == State::InProgress {
+            result.bind_result(self)
+        } else {
+            result
+        }
+    }
+
+    /// Create a new in progress action without reporting it.
+    fn unreported(
+        actioning_verb: String,
+        actioned_verb: String,
+        detail: String,
+        indent: usize,
+    ) -> Self {
+        Self {
+            state: State::InProgress,
+            actioning_verb,
+            actioned_verb,
+            detail,
+            indent,
+            should_overwrite: false,
+            elapsed_threshold: None,
+            drop_state: State::Error,
+            error_detail: ErrorDetail::Hidden,
+            id: next_id(),
+            parent_id: None,
+            started: SystemTime::now(),
+            start: Instant::now(),
+            finish: None,
+            spinner: None,
+            progress: None,
+            multi: None,
+            error: vec![],
+            warned: false,
+            children: Arc::new(ChildOutcomes::default()),
+            parent: None,
+        }
+    }
+
+    /// Create a new in progress child action without reporting it.
+    fn unreported_child(
+        &self,
+        actioning_verb: String,
+        actioned_verb: String,
+        detail: String,
+    ) -> Self {
+        let mut child = Self::unreported(
+            actioning_verb,
+            actioned_verb,
+            detail,
+            self.indent + CHILD_INDENT,
+        );
+        child.elapsed_threshold = self.elapsed_threshold;
+        child.drop_state = self.drop_state;
+        child.error_detail = self.error_detail;
+        child.parent_id = Some(self.id);
+        child.parent = Some(Arc::clone(&self.children));
+        child.multi = self.multi.clone();
+        child
+    }
+
+    /// Show the elapsed time when the action completes if it took at least `threshold`, child
+    /// actions created afterwards inherit the threshold.
+    pub fn with_elapsed(mut self, threshold: Duration) -> Self {
+        self.elapsed_threshold = Some(threshold);
+        self
+    }
+
+    /// Animate the in progress line with a spinner and the elapsed time until the state is set.
+    ///
+    /// The spinner only redraws while this action printed the last line and the output supports
+    /// cursor control. Actions in a [`MultiAction`] do not show a spinner.
+    pub fn with_spinner(mut self) -> Self {
+        if self.state == State::InProgress
+            && self.spinner.is_none()
+            && self.multi.is_none()
+            && output::format() == OutputFormat::Human
+        {
+            let line = self.message_string(State::InProgress);
+            self.spinner = Some(Spinner::start(self.id, self.start, line));
+        }
+
+        self
+    }
+
+    /// Set the state an unfinished action is set to when dropped, child actions created afterwards
+    /// inherit the state.
+    pub fn with_drop_state(mut self, state: State) -> Self {
+        self.drop_state = state;
+        self
+    }
+
+    /// Set how much of a recorded error is shown when the action errors, child actions created
+    /// afterwards inherit the detail.
+    pub fn with_error_detail(mut self, detail: ErrorDetail) -> Self {
+        self.error_detail = detail;
+        self
+    }
+
+    /// Error the action, recording the error and its sources.
+    pub fn set_error<E: Error + ?Sized>(&mut self, error: &E) {
+        self.error = vec![error.to_string()];
+
+        let mut source = error.source();
+        while let Some(error) = source {
+            self.error.push(error.to_string());
+            source = error.source();
+        }
+
+        self.set_state(State::Error);
+    }
+
+    /// Error the action, recording the error message.
+    pub fn set_error_message<M: Display>(&mut self, message: M) {
+        self.error = vec![message.to_string()];
+        self.set_state(State::Error);
+    }
+
+    /// The time since the action started, or the time the action took if it has completed.
+    pub fn elapsed(&self) -> Duration {
+        self.finish
+            .unwrap_or_else(Instant::now)
+            .saturating_duration_since(self.start)
+    }
+
+    /// Increment the position of a progress action, redrawing the bar in place.
+    pub fn inc(&mut self, amount: u64) {
+        if let Some(progress) = &self.progress {
+            let position = progress.position.saturating_add(amount);
+            self.set_position(position);
+        }
+    }
+
+    /// Set the position of a progress action, redrawing the bar in place.
+    ///
+    /// The bar is only redrawn if this action printed the last line and the output supports cursor
+    /// control, otherwise only the position is recorded. The completed line does not show the bar.
+    pub fn set_position(&mut self, position: u64) {
+        let Some(progress) = &mut self.progress else {
+            return;
+        };
+
+        progress.set_position(position);
+
+        if self.state != State::InProgress
+            || output::format() != OutputFormat::Human
+            || !progress.should_redraw()
+        {
+            return;
+        }
+
+        let message = self.message_string(State::InProgress);
+        match (&self.multi, &self.spinner) {
+            (Some(multi), _) => multi.redraw(self.id, message),
+            (None, Some(spinner)) => spinner.set_line(message),
+            (None, None) => {
+                output::redraw_line(Stream::Stdout, self.id, &message);
+            }
+        }
+    }
+
+    /// The position and total of a progress action.
+    pub fn position(&self) -> Option<(u64, u64)> {
+        self.progress
+            .as_ref()
+            .map(|progress| (progress.position, progress.total))
+    }
+
+    /// Returns if any child action has errored.
+    pub fn has_failed_child(&self) -> bool {
+        self.children.failed.load(Ordering::Relaxed)
+    }
+
+    /// Returns if this action or any child action has warnings.
+    pub fn has_warnings(&self) -> bool {
+        self.warned || self.children.warned.load(Ordering::Relaxed)
+    }
+
+    /// Mark the action as having warnings, completing it as a success will complete it as
+    /// [`State::Warning`] instead.
+    pub fn mark_warning(&mut self) {
+        self.warned = true;
+    }
+
+    /// Complete the action as skipped.
+    pub fn skip(&mut self) {
+        self.set_state(State::Skipped);
+    }
+
+    /// Complete the action as cancelled.
+    pub fn cancel(&mut self) {
+        self.set_state(State::Cancelled);
+    }
+
+    /// Update the state of the action.
+    ///
+    /// Completing the action as a success will instead error if any child action errored, or
+    /// complete with warnings if this or any child action has warnings.
+    pub fn set_state(&mut self, state: State) {
+        self.state = match state {
+            State::Success if self.has_failed_child() => State::Error,
+            State::Success if self.has_warnings() => State::Warning,
+            state => state,
+        };
+
+        self.finish = match self.state {
+            State::InProgress => None,
+            _ => Some(self.finish.unwrap_or_else(Instant::now)),
+        };
+
+        if self.state != State::InProgress
+            && let Some(spinner) = self.spinner.take()
+        {
+            spinner.stop();
+        }
+
+        if self.state != State::InProgress {
+            session::record(|| {
+                ActionRecord::new(
+                    self.id,
+                    self.state,
+                    self.actioning_verb.clone(),
+                    self.actioned_verb.clone(),
+                    self.detail.clone(),
+                    self.error.first().cloned(),
+                    self.elapsed(),
+                )
+            });
+        }
+
+        if let Some(parent) = &self.parent {
+            match self.state {
+                State::Error => parent.failed.store(true, Ordering::Relaxed),
+                State::Warning => parent.warned.store(true, Ordering::Relaxed),
+                _ => {}
+            }
+        }
+
+        self.print();
+    }
+
+    /// Print the message for this action.
+    ///
+    /// Only errors are printed in [`Verbosity::Quiet`].
+    pub fn print(&mut self) {
+        if self.state != State::Error && !output::enabled(Verbosity::Normal) {
+            return;
+        }
+
+        if output::format() == OutputFormat::Json {
+            output::write(Stream::Stdout, &self.json_line());
+            return;
+        }
+
+        let message = self.message_string(self.state);
+        if let Some(spinner) = &self.spinner {
+            spinner.set_line(message.clone());
+        }
+
+        match &self.multi {
+            Some(multi) => multi.update(self.id, message, self.state != State::InProgress),
+            None => output::write_line(Stream::Stdout, self.id, self.should_overwrite, &message),
+        }
+
+        self.should_overwrite = true;
+    }
+
+    /// Set the action to not overwrite the previous line.
+    pub fn dont_overwrite(&mut self) {
+        self.should_overwrite = false;
+    }
+
+    fn json_line(&self) -> String {
+        let mut line = JsonLine::new("action").number("id", self.id);
+        if let Some(parent_id) = self.parent_id {
+            line = line.number("parent", parent_id);
+        }
+
+        line = line
+            .string("state", self.state.name())
+            .string("actioning_verb", &self.actioning_verb)
+            .string("actioned_verb", &self.actioned_verb)
+            .string("detail", &self.detail)
+            .number("indent", u128::try_from(self.indent).unwrap_or(u128::MAX))
+            .timestamp("started", self.started)
+            .timestamp("timestamp", SystemTime::now())
+            .number("elapsed_ms", self.elapsed().as_millis());
+
+        if let Some((position, total)) = self.position() {
+            line = line.number("position", position).number("total", total);
+        }
+
+        if let Some((error, sources)) = self.error.split_first() {
+            line = line.string("error", error).strings("sources", sources);
+        }
+
+        line.finish()
+    }
+
+    fn message_string(&self, state: State) -> String {
+        let indent = " ".repeat(self.indent);
+        let actioning = &self.actioning_verb;
+        let actioned = &self.actioned_verb;
+        let detail = &self.detail;
+
+        let elapsed = match self.elapsed_threshold {
+            Some(threshold) if state != State::InProgress && self.elapsed() >= threshold => {
+                format!(" {DIM}({}){RESET}", format_duration(self.elapsed()))
+            }
+            _ => String::new(),
+        };
+
+        let (error, sources) = match (self.error_detail, self.error.split_first()) {
+            (ErrorDetail::Hidden, _) | (_, None) => (String::new(), String::new()),
+            (ErrorDetail::Message, Some((error, _))) => (format!(": {error}"), String::new()),
+            (ErrorDetail::Chain, Some((error, sources))) => {
+                let sources: String = sources
+                    .iter()
+                    .map(|source| format!("\n{indent}  {DIM}caused by:{RESET} {source}"))
+                    .collect();
+                (format!(": {error}"), sources)
+            }
+        };
+
+        let progress = match &self.progress {
+            Some(progress) if state == State::InProgress => {
+                format!(" {}", progress.render(self.elapsed()))
+            }
+            _ => String::new(),
+        };
+
+        match state {
+            State::InProgress => {
+                format!("{indent}{CYAN}{BOLD}{actioning}{RESET} {detail}{progress}")
+            }
+            State::Success => format!("{indent}{GREEN}{BOLD}{actioned}{RESET} {detail}{elapsed}"),
+            State::Error => format!(
+                "{indent}{RED}{BOLD}{actioning}{RESET} {detail} {RED}{BOLD}failed{RESET}{error}{elapsed}{sources}"
+            ),
+            State::Warning => format!(
+                "{indent}{YELLOW}{BOLD}{actioned}{RESET} {detail} {YELLOW}with warnings{RESET}{elapsed}"
+            ),
+            State::Skipped => format!("{indent}{BLUE}{BOLD}Skipped{RESET} {detail}{elapsed}"),
+            State::Cancelled => format!(
+                "{indent}{MAGENTA}{BOLD}{actioning}{RESET} {detail} {MAGENTA}{BOLD}cancelled{RESET}{elapsed}"
+            ),
+        }
+    }
+}
+
+impl Drop for Action {
+    fn drop(&mut self) {
+        if self.state == State::InProgress && self.drop_state != State::InProgress {
+            self.set_state(self.drop_state);
+        }
+
+        if let Some(spinner) = self.spinner.take() {
+            spinner.stop();
+        }
+    }
+}
+
+/// Format a duration for humans, e.g., `420ms`, `1.42s`, or `2m 05s`.
+pub(crate) fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+
+    if seconds >= 60 {
+        format!("{}m {:02}s", seconds / 60, seconds % 60)
+    } else if seconds >= 1 {
+        format!("{:.2}s", duration.as_secs_f64())
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_milliseconds() {
+        assert_eq!(format_duration(Duration::ZERO), "0ms");
+        assert_eq!(format_duration(Duration::from_millis(420)), "420ms");
+        assert_eq!(format_duration(Duration::from_millis(999)), "999ms");
+    }
+
+    #[test]
+    fn format_seconds() {
+        assert_eq!(format_duration(Duration::from_secs(1)), "1.00s");
+        assert_eq!(format_duration(Duration::from_millis(1420)), "1.42s");
+        assert_eq!(format_duration(Duration::from_millis(59_990)), "59.99s");
+    }
+
+    #[test]
+    fn format_minutes() {
+        assert_eq!(format_duration(Duration::from_secs(60)), "1m 00s");
+        assert_eq!(format_duration(Duration::from_secs(125)), "2m 05s");
+        assert_eq!(format_duration(Duration::from_secs(3725)), "62m 05s");
+    }
+}
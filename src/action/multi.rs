@@ -0,0 +1,121 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::{
+    Action,
+    action::{next_id, progress::Progress},
+    output::{self, Stream},
+};
+
+/// Displays multiple concurrent actions, redrawing their lines in place at the bottom of the
+/// output while printing completed actions above them.
+///
+/// Clones share the same region and may be used from multiple threads.
+#[derive(Clone)]
+pub struct MultiAction {
+    region: Arc<Mutex<Region>>,
+}
+
+/// The lines drawn at the bottom of the output.
+struct Region {
+    id: u64,
+    /// The in progress lines and the actions that own them, in the order they were added.
+    lines: Vec<(u64, String)>,
+}
+
+impl MultiAction {
+    /// Create a new empty multi-action display.
+    pub fn new() -> Self {
+        Self {
+            region: Arc::new(Mutex::new(Region {
+                id: next_id(),
+                lines: vec![],
+            })),
+        }
+    }
+
+    /// Create and report a new in progress action in this display.
+    pub fn action<S1: ToString, S2: ToString, S3: ToString>(
+        &self,
+        actioning_verb: S1,
+        actioned_verb: S2,
+        detail: S3,
+        indent: usize,
+    ) -> Action {
+        let mut action = Action::unreported(
+            actioning_verb.to_string(),
+            actioned_verb.to_string(),
+            detail.to_string(),
+            indent,
+        );
+        action.multi = Some(self.clone());
+        action.print();
+        action
+    }
+
+    /// Create and report a new in progress action with determinate progress in this display.
+    pub fn progress<S1: ToString, S2: ToString, S3: ToString>(
+        &self,
+        actioning_verb: S1,
+        actioned_verb: S2,
+        detail: S3,
+        indent: usize,
+        total: u64,
+    ) -> Action {
+        let mut action = Action::unreported(
+            actioning_verb.to_string(),
+            actioned_verb.to_string(),
+            detail.to_string(),
+            indent,
+        );
+        action.multi = Some(self.clone());
+        action.progress = Some(Progress::new(total));
+        action.print();
+        action
+    }
+
+    /// Update the line for an action, completed lines are printed above the in progress lines.
+    ///
+    /// If the output does not support cursor control, each update is printed on its own line.
+    pub(crate) fn update(&self, owner: u64, line: String, completed: bool) {
+        let mut region = self.region.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if !output::cursor_control_enabled(Stream::Stdout) {
+            output::write_line(Stream::Stdout, owner, false, &line);
+            return;
+        }
+
+        let position = region.lines.iter().position(|(id, _)| *id == owner);
+        let mut completed_output = String::new();
+
+        match (position, completed) {
+            (Some(index), true) => {
+                region.lines.remove(index);
+                completed_output = format!("{line}\n");
+            }
+            (None, true) => completed_output = format!("{line}\n"),
+            (Some(index), false) => region.lines[index].1 = line,
+            (None, false) => region.lines.push((owner, line)),
+        }
+
+        let pinned: String = region
+            .lines
+            .iter()
+            .map(|(_, line)| format!("{line}\n"))
+            .collect();
+
+        output::redraw_pinned(region.id, &completed_output, &pinned, region.lines.len());
+    }
+
+    /// Redraw an in progress line if the region currently owns the bottom of the output.
+    pub(crate) fn redraw(&self, owner: u64, line: String) {
+        if output::cursor_control_enabled(Stream::Stdout) {
+            self.update(owner, line, false);
+        }
+    }
+}
+
+impl Default for MultiAction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,86 @@
+use core::time::Duration;
+use std::time::Instant;
+
+use ts_rust_helper::style::*;
+
+use crate::action::format_duration;
+
+/// The number of characters in the bar.
+const BAR_WIDTH: u64 = 24;
+
+/// The minimum time between redraws, so frequent increments don't flood the output.
+const REDRAW_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Determinate progress of an action.
+pub(crate) struct Progress {
+    pub(crate) position: u64,
+    pub(crate) total: u64,
+    last_draw: Option<Instant>,
+}
+
+impl Progress {
+    pub(crate) fn new(total: u64) -> Self {
+        Self {
+            position: 0,
+            total,
+            last_draw: None,
+        }
+    }
+
+    /// Set the position, clamped to the total.
+    pub(crate) fn set_position(&mut self, position: u64) {
+        self.position = position.min(self.total);
+    }
+
+    /// Returns if the progress should be redrawn, recording the draw if it should.
+    pub(crate) fn should_redraw(&mut self) -> bool {
+        let now = Instant::now();
+
+        let should_redraw = self.position == self.total
+            || self
+                .last_draw
+                .is_none_or(|last_draw| now.duration_since(last_draw) >= REDRAW_INTERVAL);
+
+        if should_redraw {
+            self.last_draw = Some(now);
+        }
+
+        should_redraw
+    }
+
+    /// Render the bar, percentage, count, and estimated time remaining.
+    pub(crate) fn render(&self, elapsed: Duration) -> String {
+        let (position, total) = (self.position, self.total);
+
+        // Computed in `u128` so large totals cannot overflow.
+        let (filled, percent) = match u128::from(total) {
+            0 => (u128::from(BAR_WIDTH), 100),
+            total => {
+                let position = u128::from(position);
+                (
+                    position * u128::from(BAR_WIDTH) / total,
+                    position * 100 / total,
+                )
+            }
+        };
+        let filled = usize::try_from(filled).unwrap_or_default();
+        let empty = usize::try_from(BAR_WIDTH).unwrap_or_default() - filled;
+
+        let bar = format!(
+            "{CYAN}{}{RESET}{DIM}{}{RESET}",
+            "━".repeat(filled),
+            "─".repeat(empty)
+        );
+
+        let eta = if position == 0 || position >= total {
+            String::new()
+        } else {
+            let remaining =
+                elapsed.as_millis() * u128::from(total - position) / u128::from(position);
+            let remaining = Duration::from_millis(u64::try_from(remaining).unwrap_or(u64::MAX));
+            format!(" {DIM}ETA {}{RESET}", format_duration(remaining))
+        };
+
+        format!("{bar} {percent:>3}% {position}/{total}{eta}")
+    }
+}
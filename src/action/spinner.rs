@@ -0,0 +1,75 @@
+use core::time::Duration;
+use std::{
+    sync::{
+        Arc, Mutex, PoisonError,
+        mpsc::{self, RecvTimeoutError, Sender},
+    },
+    thread::{self, JoinHandle},
+    time::Instant,
+};
+
+use ts_rust_helper::style::*;
+
+use crate::{
+    action::format_duration,
+    output::{self, Stream},
+};
+
+/// The frames of the spinner animation.
+const FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// The time between spinner frames.
+const INTERVAL: Duration = Duration::from_millis(100);
+
+/// Background ticker that redraws an in progress action line with a spinner and the elapsed time.
+pub(crate) struct Spinner {
+    /// The in progress line to draw the spinner after.
+    line: Arc<Mutex<String>>,
+    /// Dropping or sending on the channel stops the ticker.
+    stop: Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+impl Spinner {
+    /// Start a spinner for the line owned by `owner`.
+    pub(crate) fn start(owner: u64, start: Instant, line: String) -> Self {
+        let line = Arc::new(Mutex::new(line));
+        let (stop, stopped) = mpsc::channel::<()>();
+
+        let handle = {
+            let line = Arc::clone(&line);
+
+            thread::spawn(move || {
+                let mut frame = 0;
+
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(INTERVAL) {
+                    let spinner = FRAMES[frame % FRAMES.len()];
+                    let elapsed = format_duration(start.elapsed());
+                    let line = line.lock().unwrap_or_else(PoisonError::into_inner);
+
+                    // If another line was printed since, the action no longer owns the last line.
+                    output::redraw_line(
+                        Stream::Stdout,
+                        owner,
+                        &format!("{line} {CYAN}{spinner}{RESET} {DIM}{elapsed}{RESET}"),
+                    );
+
+                    frame += 1;
+                }
+            })
+        };
+
+        Self { line, stop, handle }
+    }
+
+    /// Update the in progress line the spinner is drawn after.
+    pub(crate) fn set_line(&self, line: String) {
+        *self.line.lock().unwrap_or_else(PoisonError::into_inner) = line;
+    }
+
+    /// Stop the spinner, blocking until the ticker has stopped drawing.
+    pub(crate) fn stop(self) {
+        let _ = self.stop.send(());
+        let _ = self.handle.join();
+    }
+}
@@ -0,0 +1,266 @@
+use core::convert::Infallible;
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{ToTokens, format_ident, quote};
+
+use crate::{
+    ParseFileError, ParseFileErrorKind, WriteError, WriteErrorKind,
+    file_parser::{
+        ConflictPolicy, ModuleConflictError, ParseOptions, module::module_name, root_name,
+        slash_path, source::fnv1a, source_files, write::write_output,
+    },
+};
+
+/// A file embedded as-is, without parsing its contents.
+pub struct Asset {
+    /// Absolute path to the source file.
+    pub source: PathBuf,
+
+    /// Source file relative to the parent of the parsed root, so it includes the name of a root
+    /// directory, e.g., `images/logo.png`. Only the file name if the file itself was parsed.
+    pub relative_source: PathBuf,
+
+    /// Name of the module, from the full file name so `logo.png` and `logo.svg` do not collide.
+    pub name: String,
+
+    /// File name of the source file.
+    pub file_name: String,
+
+    /// Size of the file in bytes.
+    pub size: usize,
+
+    /// 64-bit FNV-1a hash of the file contents.
+    pub hash: u64,
+
+    /// If the contents are valid UTF-8, so can also be included as a `&str`.
+    pub is_text: bool,
+}
+
+impl Asset {
+    /// Read the metadata for an asset file.
+    pub fn parse(source: &Path) -> Result<Self, ParseFileError<Infallible>> {
+        let read_error = |source: std::io::Error, path: &Path| ParseFileError {
+            kind: ParseFileErrorKind::ReadFile {
+                source,
+                path: path.to_path_buf(),
+            },
+        };
+
+        let name = module_name(source.file_name(), source)?;
+
+        let contents = fs::read(source).map_err(|e| read_error(e, source))?;
+        let absolute = fs::canonicalize(source).map_err(|e| read_error(e, source))?;
+
+        let file_name = source
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        Ok(Self {
+            source: absolute,
+            relative_source: PathBuf::from(&file_name),
+            name,
+            file_name,
+            size: contents.len(),
+            hash: fnv1a(&contents),
+            is_text: core::str::from_utf8(&contents).is_ok(),
+        })
+    }
+
+    /// Generate the module for this asset, with the include path relative to `directory`.
+    ///
+    /// If `directory` is None, or there is no relative path from it, the absolute path is used.
+    pub fn to_tokens_relative_to(&self, directory: Option<&Path>) -> TokenStream {
+        let path = directory
+            .and_then(|directory| relative_path(directory, &self.source))
+            .unwrap_or_else(|| self.source.to_string_lossy().to_string());
+
+        let name = format_ident!("{}", self.name);
+        let file_name = &self.file_name;
+        let size = Literal::usize_unsuffixed(self.size);
+        let hash = syn::LitInt::new(&format!("0x{:016x}", self.hash), Span::call_site());
+        let provenance = format!(
+            " Embedded from `{}` with FNV-1a hash `0x{:016x}`.",
+            slash_path(&self.relative_source),
+            self.hash
+        );
+
+        let text = self.is_text.then(|| {
+            quote! {
+                /// The contents of the file.
+                pub static TEXT: &str = match ::core::str::from_utf8(BYTES) {
+                    Ok(text) => text,
+                    Err(_) => panic!("the asset is not valid UTF-8"),
+                };
+            }
+        });
+
+        quote! {
+            #[doc = #provenance]
+            pub mod #name {
+                /// The file name of the asset.
+                pub const NAME: &str = #file_name;
+                /// The size of the file in bytes.
+                pub const SIZE: usize = #size;
+                /// The 64-bit FNV-1a hash of the file contents.
+                pub const HASH: u64 = #hash;
+                /// The bytes of the file.
+                pub const BYTES: &[u8] = include_bytes!(#path);
+                #text
+            }
+        }
+    }
+}
+
+impl ToTokens for Asset {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(self.to_tokens_relative_to(None));
+    }
+}
+
+/// Files embedded with `include_bytes!` instead of being parsed.
+pub struct Assets {
+    /// The assets found.
+    pub assets: Vec<Asset>,
+}
+
+impl Assets {
+    /// Find assets from some source.
+    /// If source is a directory, this will be top level files in the directory, else it will be
+    /// the single file.
+    pub fn parse(source: &Path) -> Result<Self, ParseFileError<Infallible>> {
+        Self::parse_with(source, &ParseOptions::default())
+    }
+
+    /// Find assets from some source with some options, see [`Assets::parse`].
+    ///
+    /// The symlink and conflict policies apply, asset names include the file extension but still
+    /// conflict if the file names only differ in characters that are not valid in identifiers,
+    /// e.g., `a-b.txt` and `a_b.txt`.
+    pub fn parse_with(
+        source: &Path,
+        options: &ParseOptions,
+    ) -> Result<Self, ParseFileError<Infallible>> {
+        let files = source_files(source, options.symlinks)?;
+        let root_name = root_name(source, &files);
+        let mut assets: Vec<Asset> = vec![];
+        for path in &files {
+            let mut asset = Asset::parse(path)?;
+            if let Some(root_name) = &root_name {
+                asset.relative_source = root_name.join(&asset.relative_source);
+            }
+
+            let existing = assets
+                .iter()
+                .position(|existing| existing.name == asset.name);
+
+            match (existing, options.on_conflict) {
+                (None, _) => assets.push(asset),
+
+                (Some(index), ConflictPolicy::Override) => assets[index] = asset,
+
+                (Some(index), ConflictPolicy::Error) => {
+                    return Err(ParseFileError {
+                        kind: ParseFileErrorKind::ModuleConflict {
+                            source: ModuleConflictError {
+                                name: asset.name,
+                                existing: assets[index].source.clone(),
+                                conflicting: asset.source,
+                            },
+                        },
+                    });
+                }
+            }
+        }
+
+        Ok(Self { assets })
+    }
+
+    /// Generate the asset modules, with include paths relative to `directory`.
+    pub fn to_tokens_relative_to(&self, directory: Option<&Path>) -> TokenStream {
+        self.assets
+            .iter()
+            .map(|asset| asset.to_tokens_relative_to(directory))
+            .collect()
+    }
+
+    /// Write the asset modules to some target or `stdout` if None.
+    ///
+    /// Include paths are relative to the target, or absolute if writing to `stdout`.
+    pub fn write(
+        &self,
+        target: Option<&Path>,
+        generator_name: &'static str,
+        generator_version: &'static str,
+    ) -> Result<(), WriteError> {
+        let directory = match target {
+            Some(target) => {
+                let directory = match target.parent() {
+                    Some(parent) if !parent.as_os_str().is_empty() => parent,
+                    _ => Path::new("."),
+                };
+
+                let directory = fs::canonicalize(directory).map_err(|source| WriteError {
+                    kind: WriteErrorKind::ResolveTarget {
+                        path: target.to_path_buf(),
+                        source,
+                    },
+                })?;
+
+                Some(directory)
+            }
+            None => None,
+        };
+
+        write_output(
+            self.to_tokens_relative_to(directory.as_deref()),
+            target,
+            generator_name,
+            generator_version,
+        )
+    }
+}
+
+impl ToTokens for Assets {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(self.to_tokens_relative_to(None));
+    }
+}
+
+/// The path from an absolute directory to an absolute path, using `/` as the separator.
+///
+/// Returns None if the paths have no common root, e.g., they are on different drives.
+fn relative_path(directory: &Path, path: &Path) -> Option<String> {
+    let directory: Vec<_> = directory.components().collect();
+    let path: Vec<_> = path.components().collect();
+
+    let common = directory
+        .iter()
+        .zip(&path)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let has_common_root = directory
+        .iter()
+        .take(common)
+        .any(|component| matches!(component, Component::RootDir));
+    if !has_common_root {
+        return None;
+    }
+
+    let parts: Vec<_> = directory[common..]
+        .iter()
+        .map(|_| "..".to_string())
+        .chain(
+            path[common..]
+                .iter()
+                .map(|component| component.as_os_str().to_string_lossy().to_string()),
+        )
+        .collect();
+
+    Some(parts.join("/"))
+}
@@ -0,0 +1,184 @@
+use core::{error::Error, fmt, ops::Deref};
+
+#[cfg(feature = "generate")]
+use proc_macro2::TokenStream;
+#[cfg(feature = "generate")]
+use quote::ToTokens;
+use serde::de::DeserializeOwned;
+
+use crate::file_parser::ParseFrom;
+
+/// Declare a wrapper that deserializes its inner value from a data format.
+macro_rules! data_format {
+    ($(#[$attribute:meta])* $name:ident, $feature:literal, $parse:expr) => {
+        $(#[$attribute])*
+        #[cfg(feature = $feature)]
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+        pub struct $name<T>(pub T);
+
+        #[cfg(feature = $feature)]
+        impl<T> $name<T> {
+            /// Unwrap the deserialized value.
+            pub fn into_inner(self) -> T {
+                self.0
+            }
+        }
+
+        #[cfg(feature = $feature)]
+        impl<T> Deref for $name<T> {
+            type Target = T;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        #[cfg(feature = $feature)]
+        impl<T: DeserializeOwned, State: ?Sized> ParseFrom<String, State> for $name<T> {
+            type Error = DeserializeError;
+
+            fn parse(source: String, _state: &mut State) -> Result<Self, Self::Error> {
+                let parse: fn(&str) -> Result<T, DeserializeError> = $parse;
+                parse(&source).map(Self)
+            }
+        }
+
+        #[cfg(all(feature = $feature, feature = "generate"))]
+        impl<T: ToTokens> ToTokens for $name<T> {
+            fn to_tokens(&self, tokens: &mut TokenStream) {
+                self.0.to_tokens(tokens);
+            }
+        }
+    };
+}
+
+data_format!(
+    /// Parses a JSON source into the inner type.
+    Json,
+    "json",
+    |source| {
+        serde_json::from_str(source).map_err(|error| {
+            let line = error.line();
+            DeserializeError {
+                format: "JSON",
+                location: (line > 0).then(|| (line, error.column())),
+                source: Box::new(error),
+            }
+        })
+    }
+);
+
+data_format!(
+    /// Parses a TOML source into the inner type.
+    Toml,
+    "toml",
+    |source| {
+        toml::from_str(source).map_err(|error| DeserializeError {
+            format: "TOML",
+            location: error
+                .span()
+                .map(|span| line_and_column(source, span.start)),
+            source: Box::new(error),
+        })
+    }
+);
+
+data_format!(
+    /// Parses a YAML source into the inner type.
+    Yaml,
+    "yaml",
+    |source| {
+        serde_yaml::from_str(source).map_err(|error| DeserializeError {
+            format: "YAML",
+            location: error
+                .location()
+                .map(|location| (location.line(), location.column())),
+            source: Box::new(error),
+        })
+    }
+);
+
+/// Get the line and column, starting at 1, of a byte offset in the source.
+#[cfg(feature = "toml")]
+fn line_and_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = source.get(..offset).unwrap_or(source);
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map_or(0, |line| line.chars().count())
+        + 1;
+
+    (line, column)
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Failed to deserialize the source.
+pub struct DeserializeError {
+    /// The name of the data format.
+    pub format: &'static str,
+    /// The line and column of the error, starting at 1, if known.
+    pub location: Option<(usize, usize)>,
+    /// The source deserializer error.
+    pub source: Box<dyn Error + Send + Sync + 'static>,
+}
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.location {
+            Some((line, column)) => write!(
+                f,
+                "failed to deserialize the {} source at line {line}, column {column}",
+                self.format
+            ),
+            None => write!(f, "failed to deserialize the {} source", self.format),
+        }
+    }
+}
+impl Error for DeserializeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_error_location() {
+        let error =
+            Json::<serde_json::Value>::parse("{\n  \"a\": 1,\n  \"b\" 2\n}".into(), &mut ())
+                .unwrap_err();
+        assert_eq!(error.location, Some((3, 7)));
+        assert_eq!(
+            error.to_string(),
+            "failed to deserialize the JSON source at line 3, column 7"
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_error_location() {
+        // The column counts characters, not bytes.
+        let error = Toml::<toml::Table>::parse("a = 1\nb = \"é\" c\n".into(), &mut ()).unwrap_err();
+        assert_eq!(error.location, Some((2, 9)));
+        assert_eq!(
+            error.to_string(),
+            "failed to deserialize the TOML source at line 2, column 9"
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn yaml_error_location() {
+        let error =
+            Yaml::<serde_yaml::Value>::parse("a: 1\nb: [1, 2\n".into(), &mut ()).unwrap_err();
+        assert_eq!(error.location, Some((3, 1)));
+        assert_eq!(
+            error.to_string(),
+            "failed to deserialize the YAML source at line 3, column 1"
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use convert_case::{Case, Casing};
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+
+use crate::file_parser::{FileSource, Module, ParseFrom, slash_path};
+
+/// Configuration for an index generated alongside the modules of a [`FileParser`].
+///
+/// The index is an enum over the module names with `FromStr`, `Display`, and lookups for the
+/// module names and source paths.
+///
+/// [`FileParser`]: crate::FileParser
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Index {
+    /// Name of the generated enum.
+    pub enum_name: String,
+
+    /// Dispatch from the enum to an item in each module, if any.
+    pub dispatch: Option<Dispatch>,
+}
+
+/// Dispatch from an index enum to an item each module contains that implements a trait.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Dispatch {
+    /// Path to the trait, e.g., `crate::Shader`.
+    pub trait_path: String,
+
+    /// Name of the item in each module implementing the trait, e.g., `SHADER`.
+    pub item: String,
+}
+
+impl Index {
+    /// Create an index with an enum of the given name.
+    pub fn new(enum_name: &str) -> Self {
+        Self {
+            enum_name: enum_name.to_string(),
+            dispatch: None,
+        }
+    }
+
+    /// Generate `dispatch(self) -> &'static dyn Trait` returning `item` from the module.
+    ///
+    /// The item must be a constant, static, or unit struct implementing the trait.
+    pub fn with_dispatch(mut self, trait_path: &str, item: &str) -> Self {
+        self.dispatch = Some(Dispatch {
+            trait_path: trait_path.to_string(),
+            item: item.to_string(),
+        });
+        self
+    }
+
+    /// Generate the index items for the modules.
+    pub(crate) fn to_tokens<State, T: ParseFrom<S, State>, S: FileSource>(
+        &self,
+        modules: &[Module<State, T, S>],
+    ) -> TokenStream {
+        let Ok(enum_name) = syn::parse_str::<syn::Ident>(&self.enum_name) else {
+            let message = format!("`{}` is not a valid enum name", self.enum_name);
+            return quote! { compile_error!(#message); };
+        };
+        let error_name = format_ident!("Parse{}Error", enum_name);
+
+        let variants = match variants(modules, &self.enum_name) {
+            Ok(variants) => variants,
+            Err(message) => return quote! { compile_error!(#message); },
+        };
+        let names: Vec<_> = modules
+            .iter()
+            .map(|module| Literal::string(&module.name))
+            .collect();
+        // Invalid predicates are reported by the module itself.
+        let cfgs: Vec<_> = modules
+            .iter()
+            .map(|module| module.cfg_attribute().ok().flatten())
+            .collect();
+        let sources: Vec<_> = modules
+            .iter()
+            .map(|module| slash_path(&module.relative_source))
+            .collect();
+
+        let dispatch = self
+            .dispatch
+            .as_ref()
+            .map(|dispatch| dispatch.to_tokens(modules, &variants, &cfgs));
+
+        quote! {
+            /// The generated modules.
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+            pub enum #enum_name {
+                #( #cfgs #variants, )*
+            }
+
+            impl #enum_name {
+                /// Every module.
+                pub const ALL: &'static [Self] = &[ #( #cfgs Self::#variants ),* ];
+
+                /// The name and source path of every module, relative to the parent of the parsed
+                /// root.
+                pub const SOURCES: &'static [(&'static str, &'static str)] =
+                    &[ #( #cfgs (#names, #sources) ),* ];
+
+                /// The name of the module.
+                pub const fn name(self) -> &'static str {
+                    match self {
+                        #( #cfgs Self::#variants => #names, )*
+                    }
+                }
+
+                /// The source path of the module, relative to the parent of the parsed root.
+                pub const fn source(self) -> &'static str {
+                    match self {
+                        #( #cfgs Self::#variants => #sources, )*
+                    }
+                }
+
+                /// Find a module from its name.
+                pub fn from_name(name: &str) -> Option<Self> {
+                    match name {
+                        #( #cfgs #names => Some(Self::#variants), )*
+                        _ => None,
+                    }
+                }
+
+                #dispatch
+            }
+
+            impl ::core::fmt::Display for #enum_name {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.write_str(self.name())
+                }
+            }
+
+            impl ::core::str::FromStr for #enum_name {
+                type Err = #error_name;
+
+                fn from_str(value: &str) -> Result<Self, Self::Err> {
+                    Self::from_name(value).ok_or_else(|| #error_name {
+                        value: value.to_string(),
+                    })
+                }
+            }
+
+            /// The value was not the name of a module.
+            #[derive(Debug, Clone, PartialEq, Eq)]
+            pub struct #error_name {
+                /// The value that was parsed.
+                pub value: String,
+            }
+
+            impl ::core::fmt::Display for #error_name {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    write!(f, "`{}` is not a known module", self.value)
+                }
+            }
+
+            impl ::core::error::Error for #error_name {}
+        }
+    }
+}
+
+/// The enum variant for each module, reserved names are suffixed with `_`, e.g., `Self_`.
+///
+/// Returns an error if two modules produce the same variant, e.g., `v1_0` and `v10`.
+fn variants<State, T: ParseFrom<S, State>, S: FileSource>(
+    modules: &[Module<State, T, S>],
+    enum_name: &str,
+) -> Result<Vec<syn::Ident>, String> {
+    let mut variants: Vec<syn::Ident> = vec![];
+
+    for (index, module) in modules.iter().enumerate() {
+        let variant = module.name.to_case(Case::Pascal);
+        let variant = match syn::parse_str::<syn::Ident>(&variant) {
+            Ok(variant) => variant,
+            Err(_) => format_ident!("{}_", variant),
+        };
+
+        if let Some(existing) = variants.iter().position(|existing| *existing == variant) {
+            return Err(format!(
+                "modules `{}` and `{}` both produce the variant `{variant}` of `{enum_name}`, \
+                 rename one of the files",
+                modules[existing].name, modules[index].name
+            ));
+        }
+
+        variants.push(variant);
+    }
+
+    Ok(variants)
+}
+
+impl Default for Index {
+    fn default() -> Self {
+        Self::new("ModuleName")
+    }
+}
+
+impl Dispatch {
+    fn to_tokens<State, T: ParseFrom<S, State>, S: FileSource>(
+        &self,
+        modules: &[Module<State, T, S>],
+        variants: &[syn::Ident],
+        cfgs: &[Option<TokenStream>],
+    ) -> TokenStream {
+        let Ok(trait_path) = syn::parse_str::<syn::Path>(&self.trait_path) else {
+            let message = format!("`{}` is not a valid trait path", self.trait_path);
+            return quote! { compile_error!(#message); };
+        };
+        let Ok(item) = syn::parse_str::<syn::Ident>(&self.item) else {
+            let message = format!("`{}` is not a valid item name", self.item);
+            return quote! { compile_error!(#message); };
+        };
+
+        let modules = modules
+            .iter()
+            .map(|module| format_ident!("{}", module.name));
+
+        quote! {
+            /// The item of the module implementing the trait.
+            pub fn dispatch(self) -> &'static dyn #trait_path {
+                match self {
+                    #( #cfgs Self::#variants => &#modules::#item, )*
+                }
+            }
+        }
+    }
+}
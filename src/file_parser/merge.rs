@@ -0,0 +1,123 @@
+use core::{error::Error, fmt};
+use std::path::{Path, PathBuf};
+
+use crate::{
+    FileParser, ParseFileError, ParseFileErrorKind,
+    file_parser::{FileSource, Module, ParseFrom, ParseOptions},
+};
+
+/// How modules with the same name are resolved, whether the files are in the same root, e.g.,
+/// `a.txt` and `a.json`, or in different roots.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// The later module replaces the earlier module, roots are in order and files in the same
+    /// root are in file name order.
+    Override,
+
+    /// Return a [`ModuleConflictError`].
+    #[default]
+    Error,
+}
+
+impl<State, T: ParseFrom<S, State>, S: FileSource> FileParser<State, T, S> {
+    /// Parse modules from several roots, see [`FileParser::parse`].
+    ///
+    /// Modules with the same name, in the same root or a later root, are resolved using the
+    /// options' conflict policy.
+    pub fn parse_roots<P: AsRef<Path>>(
+        roots: &[P],
+        state: &mut State,
+        options: &ParseOptions,
+    ) -> Result<Self, ParseFileError<T::Error>> {
+        let mut parser = Self {
+            modules: vec![],
+            #[cfg(feature = "generate")]
+            index: None,
+        };
+
+        for root in roots {
+            let other = Self::parse_with(root.as_ref(), state, options)?;
+            parser = parser
+                .merge(other, options.on_conflict)
+                .map_err(|source| ParseFileError {
+                    kind: ParseFileErrorKind::ModuleConflict { source },
+                })?;
+        }
+
+        Ok(parser)
+    }
+
+    /// Merge the modules of another parser into this parser.
+    ///
+    /// Modules with the same name are resolved using the policy, the index of this parser is kept.
+    pub fn merge(
+        mut self,
+        other: Self,
+        policy: ConflictPolicy,
+    ) -> Result<Self, ModuleConflictError> {
+        for module in other.modules {
+            insert_module(&mut self.modules, module, policy)?;
+        }
+
+        self.modules
+            .sort_by(|a, b| a.source.file_name().cmp(&b.source.file_name()));
+
+        #[cfg(feature = "generate")]
+        if self.index.is_none() {
+            self.index = other.index;
+        }
+
+        Ok(self)
+    }
+}
+
+/// Add a module, resolving a module with the same name using the policy.
+pub(crate) fn insert_module<State, T: ParseFrom<S, State>, S: FileSource>(
+    modules: &mut Vec<Module<State, T, S>>,
+    module: Module<State, T, S>,
+    policy: ConflictPolicy,
+) -> Result<(), ModuleConflictError> {
+    let existing = modules
+        .iter()
+        .position(|existing| existing.name == module.name);
+
+    match (existing, policy) {
+        (None, _) => modules.push(module),
+
+        (Some(index), ConflictPolicy::Override) => modules[index] = module,
+
+        (Some(index), ConflictPolicy::Error) => {
+            return Err(ModuleConflictError {
+                name: module.name,
+                existing: modules[index].source.clone(),
+                conflicting: module.source,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Two source files produce a module with the same name.
+pub struct ModuleConflictError {
+    /// The name of the module.
+    pub name: String,
+    /// The source of the existing module.
+    pub existing: PathBuf,
+    /// The source of the conflicting module.
+    pub conflicting: PathBuf,
+}
+impl fmt::Display for ModuleConflictError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "module `{}` from `{}` conflicts with `{}`",
+            self.name,
+            self.conflicting.to_string_lossy(),
+            self.existing.to_string_lossy()
+        )
+    }
+}
+impl Error for ModuleConflictError {}
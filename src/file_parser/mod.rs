@@ -1,17 +1,46 @@
+#[cfg(feature = "generate")]
+mod asset;
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+mod data;
+#[cfg(feature = "generate")]
+mod index;
+mod merge;
 mod module;
+mod options;
+mod source;
+#[cfg(feature = "generate-data")]
+mod value;
 #[cfg(feature = "generate")]
 mod write;
 
 use core::{error::Error, fmt};
 use std::{
-    fs::{self},
+    collections::HashSet,
+    fs::{self, Metadata},
     io::{self},
     path::{Path, PathBuf},
 };
 
 use crate::FileType;
 
+#[cfg(feature = "generate")]
+pub use asset::{Asset, Assets};
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+pub use data::DeserializeError;
+#[cfg(feature = "json")]
+pub use data::Json;
+#[cfg(feature = "toml")]
+pub use data::Toml;
+#[cfg(feature = "yaml")]
+pub use data::Yaml;
+#[cfg(feature = "generate")]
+pub use index::{Dispatch, Index};
+pub use merge::{ConflictPolicy, ModuleConflictError};
 pub use module::{Module, to_valid_ident};
+pub use options::{ParseOptions, SymlinkPolicy};
+pub use source::FileSource;
+#[cfg(feature = "generate-data")]
+pub use value::Value;
 #[cfg(feature = "generate")]
 pub use write::{WriteError, WriteErrorKind};
 
@@ -22,81 +51,246 @@ pub trait ParseFrom<Source, State: ?Sized>: Sized {
 
     /// Try parse the source into an instance of self.
     fn parse(source: Source, state: &mut State) -> Result<Self, Self::Error>;
+
+    /// Documentation for the module generated from self, if any.
+    fn docs(&self) -> Option<String> {
+        None
+    }
 }
 
 /// Parses files into modules containing an inner parsed type.
-pub struct FileParser<State, T: ParseFrom<String, State>> {
+///
+/// Files are read into `S` before being parsed, e.g., `Vec<u8>` for binary files.
+pub struct FileParser<State, T: ParseFrom<S, State>, S: FileSource = String> {
     /// The modules parsed.
-    pub modules: Vec<Module<State, T>>,
+    pub modules: Vec<Module<State, T, S>>,
+
+    /// The index to generate alongside the modules, if any.
+    #[cfg(feature = "generate")]
+    index: Option<Index>,
 }
 
-impl<State, T: ParseFrom<String, State>> FileParser<State, T> {
+impl<State, T: ParseFrom<S, State>, S: FileSource> FileParser<State, T, S> {
     /// Parse modules from some source.
     /// If source is a directory, this will be top level files in the directory, else it will parse
     /// the single file.
     pub fn parse(source: &Path, state: &mut State) -> Result<Self, ParseFileError<T::Error>> {
-        let metadata = source.metadata().map_err(|e| ParseFileError {
-            kind: ParseFileErrorKind::ReadSourceMetadata {
-                source: e,
-                path: source.to_path_buf(),
-            },
-        })?;
+        Self::parse_with(source, state, &ParseOptions::default())
+    }
 
-        let file_type = FileType::from(&metadata);
+    /// Parse modules from some source with some options, see [`FileParser::parse`].
+    ///
+    /// Files producing the same module name, e.g., `a.txt` and `a.json`, are resolved using the
+    /// options' conflict policy.
+    pub fn parse_with(
+        source: &Path,
+        state: &mut State,
+        options: &ParseOptions,
+    ) -> Result<Self, ParseFileError<T::Error>> {
+        let mut files = source_files(source, options.symlinks)?;
 
-        let mut modules = vec![];
-        match file_type {
-            FileType::File => {
-                let module = Module::parse(source, state)?;
+        if options.sidecar_docs {
+            let sidecars: Vec<_> = files
+                .iter()
+                .map(|file| options::sidecar_path(file))
+                .collect();
+            files.retain(|file| !sidecars.contains(file));
+        }
 
-                modules.push(module);
+        let root_name = root_name(source, &files);
+        let mut modules = vec![];
+        for path in &files {
+            let mut module = Module::parse_with(path, state, options)?;
+            if let Some(root_name) = &root_name {
+                module.relative_source = root_name.join(&module.relative_source);
             }
 
-            FileType::Directory => {
-                let directory = fs::read_dir(source).map_err(|e| ParseFileError {
+            merge::insert_module(&mut modules, module, options.on_conflict).map_err(|source| {
+                ParseFileError {
+                    kind: ParseFileErrorKind::ModuleConflict { source },
+                }
+            })?;
+        }
+
+        Ok(Self {
+            modules,
+            #[cfg(feature = "generate")]
+            index: None,
+        })
+    }
+
+    /// Generate an index alongside the modules.
+    #[cfg(feature = "generate")]
+    pub fn with_index(mut self, index: Index) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// The index generated alongside the modules, if any.
+    #[cfg(feature = "generate")]
+    pub fn index(&self) -> Option<&Index> {
+        self.index.as_ref()
+    }
+
+    /// Gate each generated module behind the `#[cfg]` predicate returned for it, if any.
+    ///
+    /// The predicate can be chosen from the module's name, source path, or file metadata, e.g.,
+    /// `feature = "shaders"`. The index respects the gating.
+    #[cfg(feature = "generate")]
+    pub fn with_cfg(mut self, cfg: impl Fn(&Module<State, T, S>) -> Option<String>) -> Self {
+        for module in &mut self.modules {
+            module.cfg = cfg(module);
+        }
+        self
+    }
+}
+
+/// Find the source files for some source, sorted by file name.
+/// If source is a directory, this will be top level files in the directory, else it will be the
+/// single file. Symlinks are handled using the policy.
+fn source_files<E: Error>(
+    source: &Path,
+    symlinks: SymlinkPolicy,
+) -> Result<Vec<PathBuf>, ParseFileError<E>> {
+    let Some(metadata) = resolve_metadata(source, symlinks)? else {
+        return Ok(vec![]);
+    };
+
+    let file_type = FileType::from(&metadata);
+
+    let mut files = vec![];
+    match file_type {
+        FileType::File => files.push(source.to_path_buf()),
+
+        FileType::Directory => {
+            let directory = fs::read_dir(source).map_err(|e| ParseFileError {
+                kind: ParseFileErrorKind::ReadDirectory {
+                    source: e,
+                    path: source.to_path_buf(),
+                },
+            })?;
+
+            for entry in directory {
+                let entry = entry.map_err(|e| ParseFileError {
                     kind: ParseFileErrorKind::ReadDirectory {
                         source: e,
-                        path: source.to_path_buf(),
+                        path: source.join("?").to_path_buf(),
                     },
                 })?;
 
-                for entry in directory {
-                    let entry = entry.map_err(|e| ParseFileError {
-                        kind: ParseFileErrorKind::ReadDirectory {
-                            source: e,
-                            path: source.join("?").to_path_buf(),
-                        },
-                    })?;
-
-                    if !entry.file_type().expect("File must have type").is_file() {
-                        continue;
-                    }
+                let entry_type = entry.file_type().map_err(|e| ParseFileError {
+                    kind: ParseFileErrorKind::ReadFileType {
+                        source: e,
+                        path: entry.path(),
+                    },
+                })?;
 
-                    let module = Module::parse(entry.path().as_path(), state)?;
+                let is_file = if entry_type.is_symlink() {
+                    resolve_metadata(&entry.path(), symlinks)?
+                        .is_some_and(|metadata| metadata.is_file())
+                } else {
+                    entry_type.is_file()
+                };
 
-                    modules.push(module)
+                if !is_file {
+                    continue;
                 }
-            }
 
-            file_type => {
-                return Err(ParseFileError {
-                    kind: ParseFileErrorKind::UnsupportedFileType {
-                        file_type,
-                        path: source.to_path_buf(),
-                    },
-                });
+                files.push(entry.path());
             }
-        };
+        }
 
-        modules.sort_by(|a, b| {
-            a.source
-                .file_name()
-                .unwrap()
-                .cmp(b.source.file_name().unwrap())
-        });
+        file_type => {
+            return Err(ParseFileError {
+                kind: ParseFileErrorKind::UnsupportedFileType {
+                    file_type,
+                    path: source.to_path_buf(),
+                },
+            });
+        }
+    };
+
+    files.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
 
-        Ok(Self { modules })
+    Ok(files)
+}
+
+/// The name of the root directory, None if the root is a file or has no name, e.g., `/`.
+pub(crate) fn root_name(root: &Path, files: &[PathBuf]) -> Option<PathBuf> {
+    if files.iter().any(|file| file == root) {
+        return None;
     }
+
+    // Canonicalized so roots like `shaders/..` have a name.
+    fs::canonicalize(root).ok()?.file_name().map(PathBuf::from)
+}
+
+/// A relative path using `/` as the separator, so generated code is the same on every platform.
+#[cfg(feature = "generate")]
+pub(crate) fn slash_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// The maximum number of symlinks followed for a single path before it is treated as a loop.
+const MAX_SYMLINK_HOPS: usize = 40;
+
+/// Read the metadata for a path, handling symlinks using the policy.
+///
+/// Returns None if the path is a symlink that should be skipped.
+fn resolve_metadata<E: Error>(
+    path: &Path,
+    symlinks: SymlinkPolicy,
+) -> Result<Option<Metadata>, ParseFileError<E>> {
+    let read_error = |source: io::Error, path: &Path| ParseFileError {
+        kind: ParseFileErrorKind::ReadSourceMetadata {
+            source,
+            path: path.to_path_buf(),
+        },
+    };
+
+    let mut metadata = fs::symlink_metadata(path).map_err(|e| read_error(e, path))?;
+    if !metadata.is_symlink() {
+        return Ok(Some(metadata));
+    }
+
+    match symlinks {
+        SymlinkPolicy::Skip => return Ok(None),
+
+        SymlinkPolicy::Error => {
+            return Err(ParseFileError {
+                kind: ParseFileErrorKind::Symlink {
+                    path: path.to_path_buf(),
+                },
+            });
+        }
+
+        SymlinkPolicy::Follow => {}
+    }
+
+    let mut visited = HashSet::new();
+    let mut current = path.to_path_buf();
+    while metadata.is_symlink() {
+        if visited.len() >= MAX_SYMLINK_HOPS || !visited.insert(current.clone()) {
+            return Err(ParseFileError {
+                kind: ParseFileErrorKind::SymlinkLoop {
+                    path: path.to_path_buf(),
+                },
+            });
+        }
+
+        let target = fs::read_link(&current).map_err(|e| read_error(e, &current))?;
+        current = match current.parent() {
+            Some(parent) => parent.join(target),
+            None => target,
+        };
+
+        metadata = fs::symlink_metadata(&current).map_err(|e| read_error(e, &current))?;
+    }
+
+    Ok(Some(metadata))
 }
 
 #[derive(Debug)]
@@ -148,6 +342,36 @@ pub enum ParseFileErrorKind<E: Error + 'static> {
         path: PathBuf,
     },
 
+    #[non_exhaustive]
+    /// Failed to read the file type of a directory entry.
+    ReadFileType {
+        /// The source IO error.
+        source: io::Error,
+        /// The path to the entry.
+        path: PathBuf,
+    },
+
+    #[non_exhaustive]
+    /// A symlink was found and the policy is to error.
+    Symlink {
+        /// The path to the symlink.
+        path: PathBuf,
+    },
+
+    #[non_exhaustive]
+    /// A symlink could not be resolved as it forms a loop.
+    SymlinkLoop {
+        /// The path to the symlink.
+        path: PathBuf,
+    },
+
+    #[non_exhaustive]
+    /// A source file name does not produce a valid module name.
+    InvalidFileName {
+        /// The path to the source.
+        path: PathBuf,
+    },
+
     #[non_exhaustive]
     /// Failed to read one of the source files.
     ReadFile {
@@ -165,6 +389,13 @@ pub enum ParseFileErrorKind<E: Error + 'static> {
         /// The path to the source.
         path: PathBuf,
     },
+
+    #[non_exhaustive]
+    /// Two files produce a module with the same name.
+    ModuleConflict {
+        /// The source conflict error.
+        source: ModuleConflictError,
+    },
 }
 impl<E: Error> fmt::Display for ParseFileErrorKind<E> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -189,6 +420,22 @@ impl<E: Error> fmt::Display for ParseFileErrorKind<E> {
                     path.to_string_lossy()
                 )
             }
+            Self::ReadFileType { path, .. } => write!(
+                f,
+                "failed reading the file type of `{}`",
+                path.to_string_lossy()
+            ),
+            Self::Symlink { path } => {
+                write!(f, "`{}` is a symlink", path.to_string_lossy())
+            }
+            Self::SymlinkLoop { path } => {
+                write!(f, "symlink `{}` forms a loop", path.to_string_lossy())
+            }
+            Self::InvalidFileName { path } => write!(
+                f,
+                "`{}` does not have a file name that can be used as a module name",
+                path.to_string_lossy()
+            ),
             Self::ReadFile { path, .. } => {
                 write!(f, "failed reading the file `{}`", path.to_string_lossy())
             }
@@ -197,6 +444,9 @@ impl<E: Error> fmt::Display for ParseFileErrorKind<E> {
                 "failed to parse the contents of `{}`",
                 path.to_string_lossy()
             ),
+            Self::ModuleConflict { source } => {
+                write!(f, "conflicting modules named `{}`", source.name)
+            }
         }
     }
 }
@@ -205,8 +455,10 @@ impl<E: Error> Error for ParseFileErrorKind<E> {
         match &self {
             Self::ReadSourceMetadata { source, .. } => Some(source),
             Self::ReadDirectory { source, .. } => Some(source),
+            Self::ReadFileType { source, .. } => Some(source),
             Self::ReadFile { source, .. } => Some(source),
             Self::ParseContents { source, .. } => Some(source),
+            Self::ModuleConflict { source } => Some(source),
             _ => None,
         }
     }
@@ -1,5 +1,6 @@
-use core::{cell::LazyCell, marker::PhantomData};
+use core::{cell::LazyCell, error::Error, marker::PhantomData};
 use std::{
+    ffi::OsStr,
     fs,
     path::{Path, PathBuf},
 };
@@ -12,38 +13,119 @@ use proc_macro2::TokenStream;
 #[cfg(feature = "generate")]
 use quote::{ToTokens, format_ident, quote};
 
-use crate::{ParseFileError, ParseFileErrorKind, file_parser::ParseFrom};
+#[cfg(feature = "generate")]
+use crate::file_parser::slash_path;
+use crate::{
+    ParseFileError, ParseFileErrorKind,
+    file_parser::{FileSource, ParseFrom, ParseOptions, options, source::fnv1a},
+};
 
-/// Convert a string to a valid `ident`.
+/// Keywords that cannot be used as an `ident`, including reserved keywords.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Convert a string to a valid `ident`, keywords are suffixed with `_`.
+///
+/// Returns an empty string if the name contains no valid characters.
 pub fn to_valid_ident(name: &str) -> String {
     let invalid_characters: LazyCell<Regex> =
         LazyCell::new(|| Regex::new(r"(?:^[^a-zA-Z_]+|[^a-zA-Z0-9_]+)").unwrap());
-    invalid_characters
+    let ident = invalid_characters
         .replace_all(name.trim(), "_")
-        .to_case(Case::Snake)
+        .to_case(Case::Snake);
+
+    if KEYWORDS.contains(&ident.as_str()) {
+        format!("{ident}_")
+    } else {
+        ident
+    }
+}
+
+/// Create a module name from part of a path, the path must produce a non-empty `ident`.
+pub(crate) fn module_name<E: Error>(
+    name: Option<&OsStr>,
+    path: &Path,
+) -> Result<String, ParseFileError<E>> {
+    let name = name.map(|name| to_valid_ident(&name.to_string_lossy()));
+
+    match name {
+        Some(name) if !name.is_empty() => Ok(name),
+        _ => Err(ParseFileError {
+            kind: ParseFileErrorKind::InvalidFileName {
+                path: path.to_path_buf(),
+            },
+        }),
+    }
 }
 
 /// A module with a name, and parsed contents.
-pub struct Module<State, T: ParseFrom<String, State>> {
+pub struct Module<State, T: ParseFrom<S, State>, S: FileSource = String> {
     /// Parsed contents of the module.
     pub contents: T,
 
     /// Source file for the module.
     pub source: PathBuf,
 
+    /// Source file relative to the parent of the parsed root, so it includes the name of a root
+    /// directory, e.g., `shaders/blur.wgsl`. Only the file name if the file itself was parsed.
+    pub relative_source: PathBuf,
+
     /// Name of the module.
     pub name: String,
 
-    phantom_data: PhantomData<State>,
+    /// Documentation for the module.
+    pub docs: Option<String>,
+
+    /// 64-bit FNV-1a hash of the source file contents.
+    pub hash: u64,
+
+    /// `#[cfg]` predicate the generated module is gated behind, e.g., `feature = "shaders"`.
+    #[cfg(feature = "generate")]
+    pub cfg: Option<String>,
+
+    phantom_data: PhantomData<(State, S)>,
 }
 
 #[cfg(feature = "generate")]
-impl<State, T: ToTokens + ParseFrom<String, State>> ToTokens for Module<State, T> {
+impl<State, T: ToTokens + ParseFrom<S, State>, S: FileSource> ToTokens for Module<State, T, S> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let name = format_ident!("{}", self.name);
         let contents = &self.contents;
 
+        let provenance = format!(
+            " Generated from `{}` with FNV-1a hash `0x{:016x}`.",
+            slash_path(&self.relative_source),
+            self.hash
+        );
+        let docs = self
+            .docs
+            .iter()
+            .flat_map(|docs| docs.trim_end().lines().chain([""]))
+            .map(|line| {
+                if line.is_empty() {
+                    String::new()
+                } else {
+                    format!(" {line}")
+                }
+            });
+
+        let cfg = match self.cfg_attribute() {
+            Ok(cfg) => cfg,
+            Err(message) => {
+                tokens.extend(quote! { compile_error!(#message); });
+                return;
+            }
+        };
+
         let new_tokens = quote! {
+            #( #[doc = #docs] )*
+            #[doc = #provenance]
+            #cfg
             pub mod #name {
                 #contents
             }
@@ -53,23 +135,53 @@ impl<State, T: ToTokens + ParseFrom<String, State>> ToTokens for Module<State, T
     }
 }
 
-impl<State, T: ParseFrom<String, State>> ParseFrom<&Path, State> for Module<State, T> {
+#[cfg(feature = "generate")]
+impl<State, T: ParseFrom<S, State>, S: FileSource> Module<State, T, S> {
+    /// The `#[cfg]` attribute for the module, if it is gated.
+    pub(crate) fn cfg_attribute(&self) -> Result<Option<TokenStream>, String> {
+        let Some(cfg) = self.cfg.as_deref() else {
+            return Ok(None);
+        };
+
+        let predicate = syn::parse_str::<syn::Meta>(cfg)
+            .map_err(|_| format!("`{cfg}` is not a valid `cfg` predicate for `{}`", self.name))?;
+
+        Ok(Some(quote! { #[cfg(#predicate)] }))
+    }
+}
+
+impl<State, T: ParseFrom<S, State>, S: FileSource> ParseFrom<&Path, State> for Module<State, T, S> {
     type Error = ParseFileError<T::Error>;
 
     fn parse(source: &Path, state: &mut State) -> Result<Self, Self::Error> {
-        let contents = fs::read_to_string(source).map_err(|e| ParseFileError {
+        Self::parse_with(source, state, &ParseOptions::default())
+    }
+}
+
+impl<State, T: ParseFrom<S, State>, S: FileSource> Module<State, T, S> {
+    /// Parse a module from a source file with some options.
+    ///
+    /// The module docs come from the parsed contents, then the sidecar file, then the leading
+    /// comment, whichever is found first.
+    pub fn parse_with(
+        source: &Path,
+        state: &mut State,
+        options: &ParseOptions,
+    ) -> Result<Self, ParseFileError<T::Error>> {
+        let name = module_name(source.file_stem(), source)?;
+
+        let contents = S::read(source).map_err(|e| ParseFileError {
             kind: ParseFileErrorKind::ReadFile {
                 source: e,
                 path: source.to_path_buf(),
             },
         })?;
 
-        let name = to_valid_ident(
-            &source
-                .file_stem()
-                .expect("Path must have file")
-                .to_string_lossy(),
-        );
+        let hash = fnv1a(contents.as_bytes());
+        let leading_comment = options
+            .leading_comment
+            .as_deref()
+            .and_then(|prefix| options::leading_comment(contents.as_bytes(), prefix));
 
         let contents = T::parse(contents, state).map_err(|e| ParseFileError {
             kind: ParseFileErrorKind::ParseContents {
@@ -78,10 +190,31 @@ impl<State, T: ParseFrom<String, State>> ParseFrom<&Path, State> for Module<Stat
             },
         })?;
 
+        let sidecar = if options.sidecar_docs {
+            let path = options::sidecar_path(source);
+            if path.is_file() {
+                let docs = fs::read_to_string(&path).map_err(|e| ParseFileError {
+                    kind: ParseFileErrorKind::ReadFile { source: e, path },
+                })?;
+                Some(docs)
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        let docs = contents.docs().or(sidecar).or(leading_comment);
+
         Ok(Self {
             contents,
             source: source.to_path_buf(),
+            relative_source: source.file_name().map(PathBuf::from).unwrap_or_default(),
             name,
+            docs,
+            hash,
+            #[cfg(feature = "generate")]
+            cfg: None,
             phantom_data: Default::default(),
         })
     }
@@ -0,0 +1,90 @@
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+use crate::file_parser::ConflictPolicy;
+
+/// Options for parsing files into modules.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ParseOptions {
+    /// Prefix of the line comments at the start of a file to use as the module docs, e.g., `//`.
+    pub leading_comment: Option<String>,
+
+    /// If a sidecar file, e.g., `shader.glsl.md` for `shader.glsl`, is used as the module docs.
+    /// Sidecar files are not parsed as modules.
+    pub sidecar_docs: bool,
+
+    /// How modules with the same name, from the same root or different roots, are resolved.
+    pub on_conflict: ConflictPolicy,
+
+    /// How symlinks, including the root, are handled.
+    pub symlinks: SymlinkPolicy,
+}
+
+/// How symlinks are handled when finding source files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SymlinkPolicy {
+    /// Use the file or directory the symlink points to.
+    #[default]
+    Follow,
+
+    /// Ignore the symlink, a symlinked root produces no modules.
+    Skip,
+
+    /// Return an error.
+    Error,
+}
+
+impl ParseOptions {
+    /// Use the line comments with the prefix at the start of a file as the module docs.
+    pub fn with_leading_comment(mut self, prefix: &str) -> Self {
+        self.leading_comment = Some(prefix.to_string());
+        self
+    }
+
+    /// Use a sidecar `.md` file next to the source file as the module docs.
+    pub fn with_sidecar_docs(mut self) -> Self {
+        self.sidecar_docs = true;
+        self
+    }
+
+    /// Set how symlinks are handled.
+    pub fn with_symlink_policy(mut self, policy: SymlinkPolicy) -> Self {
+        self.symlinks = policy;
+        self
+    }
+
+    /// Set how modules with the same name, from the same root or different roots, are resolved.
+    pub fn with_conflict_policy(mut self, policy: ConflictPolicy) -> Self {
+        self.on_conflict = policy;
+        self
+    }
+}
+
+/// The path of the sidecar docs for a source file.
+pub(crate) fn sidecar_path(source: &Path) -> PathBuf {
+    let mut path = OsString::from(source.as_os_str());
+    path.push(".md");
+    PathBuf::from(path)
+}
+
+/// The contents of the line comments with the prefix at the start of the source.
+pub(crate) fn leading_comment(source: &[u8], prefix: &str) -> Option<String> {
+    let source = core::str::from_utf8(source).ok()?;
+    let source = source.strip_prefix('\u{feff}').unwrap_or(source);
+
+    let lines: Vec<_> = source
+        .lines()
+        .skip_while(|line| line.trim().is_empty())
+        .map_while(|line| line.trim_start().strip_prefix(prefix))
+        .map(|line| line.strip_prefix(' ').unwrap_or(line).trim_end())
+        .collect();
+
+    if lines.iter().all(|line| line.is_empty()) {
+        return None;
+    }
+
+    Some(lines.join("\n"))
+}
@@ -0,0 +1,39 @@
+use std::{fs, io, path::Path};
+
+/// A type the contents of a file can be read into, before being parsed.
+pub trait FileSource: Sized {
+    /// Read the file at the path.
+    fn read(path: &Path) -> io::Result<Self>;
+
+    /// The raw bytes read from the file.
+    fn as_bytes(&self) -> &[u8];
+}
+
+/// 64-bit FNV-1a hash.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+impl FileSource for String {
+    /// Read the file as UTF-8 text.
+    fn read(path: &Path) -> io::Result<Self> {
+        fs::read_to_string(path)
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+}
+
+impl FileSource for Vec<u8> {
+    /// Read the bytes of the file.
+    fn read(path: &Path) -> io::Result<Self> {
+        fs::read(path)
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+}
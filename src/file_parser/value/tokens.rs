@@ -0,0 +1,581 @@
+use core::{fmt, str::FromStr};
+use std::collections::{HashMap, HashSet};
+
+use convert_case::{Case, Casing};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::{ToTokens, format_ident, quote};
+
+use crate::{file_parser::Value, to_valid_ident};
+
+/// The inferred Rust type of a value.
+#[derive(Debug, Clone, PartialEq)]
+enum Shape {
+    /// No value was seen, e.g., the elements of an empty sequence.
+    Unknown,
+    Null,
+    Bool,
+    /// Integers that are all non-negative and fit in an `i64`, so they are also valid as `u64`.
+    NonNegative,
+    Signed,
+    Unsigned,
+    Float,
+    Str,
+    Option(Box<Self>),
+    Array(Box<Self>),
+    /// A map with identifier keys.
+    Struct {
+        name: String,
+        fields: Vec<(String, Self)>,
+    },
+    /// A map with arbitrary keys and values of the same type.
+    Table {
+        name: String,
+        value: Box<Self>,
+    },
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown => write!(f, "unknown"),
+            Self::Null => write!(f, "null"),
+            Self::Bool => write!(f, "bool"),
+            Self::NonNegative | Self::Signed => write!(f, "i64"),
+            Self::Unsigned => write!(f, "u64"),
+            Self::Float => write!(f, "f64"),
+            Self::Str => write!(f, "string"),
+            Self::Option(inner) => write!(f, "optional {inner}"),
+            Self::Array(inner) => write!(f, "array of {inner}"),
+            Self::Struct { name, .. } => write!(f, "struct `{name}`"),
+            Self::Table { name, .. } => write!(f, "table `{name}`"),
+        }
+    }
+}
+
+/// Returns if a map key can be used as a struct field.
+fn is_field_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+}
+
+/// Create an identifier for a field, using a raw identifier for keywords.
+fn field_ident(name: &str) -> Ident {
+    if syn::parse_str::<Ident>(name).is_ok() {
+        return Ident::new(name, Span::call_site());
+    }
+
+    match name {
+        "self" | "Self" | "super" | "crate" | "_" => format_ident!("{}_", name),
+        name => Ident::new_raw(name, Span::call_site()),
+    }
+}
+
+/// Create the identifier for a type name.
+fn type_ident(name: &str) -> Ident {
+    format_ident!("{}", name.to_case(Case::Pascal))
+}
+
+impl Shape {
+    /// Infer the shape of a value, `name` is used for any struct or table types.
+    fn infer(value: &Value, name: &str) -> Result<Self, String> {
+        let shape = match value {
+            Value::Null => Self::Null,
+            Value::Bool(_) => Self::Bool,
+            Value::Integer(value) if *value >= 0 => Self::NonNegative,
+            Value::Integer(_) => Self::Signed,
+            Value::Unsigned(_) => Self::Unsigned,
+            Value::Float(_) => Self::Float,
+            Value::String(_) => Self::Str,
+
+            Value::Sequence(values) => {
+                let item_name = format!("{name}_item");
+                let mut item = Self::Unknown;
+                for value in values {
+                    item = item.unify(Self::infer(value, &item_name)?)?;
+                }
+                Self::Array(Box::new(item))
+            }
+
+            Value::Map(entries) => {
+                let fields: Vec<_> = entries.iter().map(|(key, _)| to_valid_ident(key)).collect();
+                let unique_fields: HashSet<_> = fields.iter().collect();
+
+                let is_struct = entries.iter().all(|(key, _)| is_field_key(key))
+                    && unique_fields.len() == fields.len()
+                    && fields.iter().all(|field| !field.is_empty());
+
+                if is_struct {
+                    let fields = entries
+                        .iter()
+                        .zip(fields)
+                        .map(|((_, value), field)| {
+                            let shape = Self::infer(value, &format!("{name}_{field}"))?;
+                            Ok((field, shape))
+                        })
+                        .collect::<Result<_, String>>()?;
+
+                    Self::Struct {
+                        name: name.to_string(),
+                        fields,
+                    }
+                } else {
+                    let value_name = format!("{name}_value");
+                    let mut value_shape = Self::Unknown;
+                    for (_, value) in entries {
+                        value_shape = value_shape.unify(Self::infer(value, &value_name)?)?;
+                    }
+
+                    Self::Table {
+                        name: name.to_string(),
+                        value: Box::new(value_shape),
+                    }
+                }
+            }
+        };
+
+        Ok(shape)
+    }
+
+    /// Find a shape that can represent values of both shapes.
+    fn unify(self, other: Self) -> Result<Self, String> {
+        let shape = match (self, other) {
+            (Self::Unknown, shape) | (shape, Self::Unknown) => shape,
+            (Self::Null, Self::Null) => Self::Null,
+            (Self::Null, Self::Option(shape)) | (Self::Option(shape), Self::Null) => {
+                Self::Option(shape)
+            }
+            (Self::Null, shape) | (shape, Self::Null) => Self::Option(Box::new(shape)),
+            (Self::Option(a), Self::Option(b)) => Self::Option(Box::new(a.unify(*b)?)),
+            (Self::Option(a), b) | (b, Self::Option(a)) => Self::Option(Box::new(a.unify(b)?)),
+
+            (Self::NonNegative, Self::Signed) | (Self::Signed, Self::NonNegative) => Self::Signed,
+            (Self::NonNegative, Self::Unsigned) | (Self::Unsigned, Self::NonNegative) => {
+                Self::Unsigned
+            }
+            (Self::Signed, Self::Unsigned) | (Self::Unsigned, Self::Signed) => {
+                return Err(
+                    "integers are too large for an i64 and negative integers do not fit in a u64"
+                        .to_string(),
+                );
+            }
+            (Self::Float, Self::NonNegative | Self::Signed | Self::Unsigned)
+            | (Self::NonNegative | Self::Signed | Self::Unsigned, Self::Float) => Self::Float,
+
+            (Self::Array(a), Self::Array(b)) => Self::Array(Box::new(a.unify(*b)?)),
+
+            (Self::Struct { name, fields: a }, Self::Struct { fields: mut b, .. }) => {
+                let mut fields = vec![];
+                for (field, shape) in a {
+                    let shape = match b.iter().position(|(other, _)| *other == field) {
+                        Some(index) => shape.unify(b.remove(index).1)?,
+                        None => shape.unify(Self::Null)?,
+                    };
+                    fields.push((field, shape));
+                }
+                for (field, shape) in b {
+                    fields.push((field, shape.unify(Self::Null)?));
+                }
+
+                Self::Struct { name, fields }
+            }
+
+            (Self::Table { name, value: a }, Self::Table { value: b, .. }) => Self::Table {
+                name,
+                value: Box::new(a.unify(*b)?),
+            },
+
+            (a, b) if a == b => a,
+            (a, b) => return Err(format!("incompatible types {a} and {b}")),
+        };
+
+        Ok(shape)
+    }
+
+    /// The Rust type for the shape.
+    fn type_tokens(&self) -> TokenStream {
+        match self {
+            Self::Unknown => quote! { () },
+            Self::Null => quote! { Option<()> },
+            Self::Bool => quote! { bool },
+            Self::NonNegative | Self::Signed => quote! { i64 },
+            Self::Unsigned => quote! { u64 },
+            Self::Float => quote! { f64 },
+            Self::Str => quote! { &'static str },
+            Self::Option(inner) => {
+                let inner = inner.type_tokens();
+                quote! { Option<#inner> }
+            }
+            Self::Array(inner) => {
+                let inner = inner.type_tokens();
+                quote! { &'static [#inner] }
+            }
+            Self::Struct { name, .. } | Self::Table { name, .. } => {
+                type_ident(name).to_token_stream()
+            }
+        }
+    }
+
+    /// Add the struct and table definitions needed by this shape.
+    ///
+    /// `defined` maps the type names already defined to their shapes, a different shape with the
+    /// same type name is an error, e.g., from the keys `a_b` and `a.b`.
+    fn definitions(
+        &self,
+        defined: &mut HashMap<String, Self>,
+        tokens: &mut TokenStream,
+    ) -> Result<(), String> {
+        match self {
+            Self::Option(inner) | Self::Array(inner) => inner.definitions(defined, tokens)?,
+
+            Self::Struct { name, fields } => {
+                if !self.define(name, defined)? {
+                    return Ok(());
+                }
+
+                let ident = type_ident(name);
+                let field_idents = fields.iter().map(|(field, _)| field_ident(field));
+                let field_types = fields.iter().map(|(_, shape)| shape.type_tokens());
+
+                tokens.extend(quote! {
+                    #[derive(Debug, Clone, Copy, PartialEq)]
+                    pub struct #ident {
+                        #( pub #field_idents: #field_types, )*
+                    }
+                });
+
+                for (_, shape) in fields {
+                    shape.definitions(defined, tokens)?;
+                }
+            }
+
+            Self::Table { name, value } => {
+                if !self.define(name, defined)? {
+                    return Ok(());
+                }
+
+                let ident = type_ident(name);
+                let value_type = value.type_tokens();
+
+                tokens.extend(quote! {
+                    /// Lookup table with entries sorted by key.
+                    #[derive(Debug, Clone, Copy, PartialEq)]
+                    pub struct #ident {
+                        pub entries: &'static [(&'static str, #value_type)],
+                    }
+
+                    impl #ident {
+                        /// Get the value for a key.
+                        pub fn get(&self, key: &str) -> Option<&'static #value_type> {
+                            self.entries
+                                .binary_search_by(|(entry, _)| (*entry).cmp(key))
+                                .ok()
+                                .map(|index| &self.entries[index].1)
+                        }
+                    }
+                });
+
+                value.definitions(defined, tokens)?;
+            }
+
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Record the type name of this shape, returns if it still needs to be defined.
+    fn define(&self, name: &str, defined: &mut HashMap<String, Self>) -> Result<bool, String> {
+        let type_name = type_ident(name).to_string();
+        match defined.get(&type_name) {
+            Some(existing) if existing == self => Ok(false),
+            Some(_) => Err(format!(
+                "different types are both named `{type_name}`, rename the keys so they are distinct"
+            )),
+            None => {
+                defined.insert(type_name, self.clone());
+                Ok(true)
+            }
+        }
+    }
+
+    /// The Rust expression for a value of this shape.
+    fn expression(&self, value: &Value) -> Result<TokenStream, String> {
+        let expression = match (self, value) {
+            (Self::Option(_) | Self::Null, Value::Null) => quote! { None },
+            (Self::Option(inner), value) => {
+                let inner = inner.expression(value)?;
+                quote! { Some(#inner) }
+            }
+
+            (Self::Bool, Value::Bool(value)) => value.to_token_stream(),
+            (Self::NonNegative | Self::Signed, Value::Integer(value)) => {
+                Literal::i64_suffixed(*value).into_token_stream()
+            }
+            (Self::Signed, Value::Unsigned(value)) => {
+                let value = i64::try_from(*value)
+                    .map_err(|_| format!("`{value}` does not fit in an i64"))?;
+                Literal::i64_suffixed(value).into_token_stream()
+            }
+            (Self::Unsigned, Value::Integer(value)) => {
+                let value = u64::try_from(*value)
+                    .map_err(|_| format!("`{value}` does not fit in a u64"))?;
+                Literal::u64_suffixed(value).into_token_stream()
+            }
+            (Self::Unsigned, Value::Unsigned(value)) => {
+                Literal::u64_suffixed(*value).into_token_stream()
+            }
+            (Self::Float, Value::Integer(value)) => float_literal(&value.to_string())?,
+            (Self::Float, Value::Unsigned(value)) => float_literal(&value.to_string())?,
+            (Self::Float, Value::Float(value)) if value.is_nan() => quote! { f64::NAN },
+            (Self::Float, Value::Float(value)) if value.is_infinite() => {
+                if value.is_sign_positive() {
+                    quote! { f64::INFINITY }
+                } else {
+                    quote! { f64::NEG_INFINITY }
+                }
+            }
+            (Self::Float, Value::Float(value)) => Literal::f64_suffixed(*value).into_token_stream(),
+            (Self::Str, Value::String(value)) => Literal::string(value).into_token_stream(),
+
+            (Self::Array(inner), Value::Sequence(values)) => {
+                let values = values
+                    .iter()
+                    .map(|value| inner.expression(value))
+                    .collect::<Result<Vec<_>, _>>()?;
+                quote! { &[ #( #values ),* ] }
+            }
+
+            (Self::Struct { name, fields }, Value::Map(entries)) => {
+                let ident = type_ident(name);
+                let values = fields
+                    .iter()
+                    .map(|(field, shape)| {
+                        let value = entries
+                            .iter()
+                            .find(|(key, _)| to_valid_ident(key) == *field)
+                            .map_or(&Value::Null, |(_, value)| value);
+
+                        let field = field_ident(field);
+                        let value = shape.expression(value)?;
+                        Ok(quote! { #field: #value })
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+
+                quote! { #ident { #( #values ),* } }
+            }
+
+            (Self::Table { name, value: shape }, Value::Map(entries)) => {
+                let ident = type_ident(name);
+
+                let mut entries: Vec<_> = entries.iter().collect();
+                entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+                let entries = entries
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let value = shape.expression(value)?;
+                        Ok(quote! { (#key, #value) })
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+
+                quote! { #ident { entries: &[ #( #entries ),* ] } }
+            }
+
+            (shape, value) => return Err(format!("value `{value:?}` is not a {shape}")),
+        };
+
+        Ok(expression)
+    }
+}
+
+/// Create a float literal from the digits of an integer.
+fn float_literal(digits: &str) -> Result<TokenStream, String> {
+    Literal::from_str(&format!("{digits}f64"))
+        .map(ToTokens::into_token_stream)
+        .map_err(|error| error.to_string())
+}
+
+impl Value {
+    /// Generate a `pub static` item named `name` holding this value, preceded by the struct and
+    /// table definitions it needs.
+    ///
+    /// Maps with identifier keys become structs, other maps become lookup tables, sequences become
+    /// slices, and scalars keep their types. If the value cannot be represented, e.g., a sequence
+    /// mixes strings and numbers, or `name` does not form a valid identifier, a `compile_error!`
+    /// is generated instead.
+    pub fn to_static_tokens(&self, name: &str) -> TokenStream {
+        match self.try_static_tokens(name) {
+            Ok(tokens) => tokens,
+            Err(message) => {
+                let message = format!("cannot generate `{name}`: {message}");
+                quote! { compile_error!(#message); }
+            }
+        }
+    }
+
+    fn try_static_tokens(&self, name: &str) -> Result<TokenStream, String> {
+        // Type names are derived from the name, so both forms must be valid identifiers.
+        for ident in [name.to_case(Case::UpperSnake), name.to_case(Case::Pascal)] {
+            if syn::parse_str::<Ident>(&ident).is_err() {
+                return Err(format!("`{ident}` is not a valid identifier"));
+            }
+        }
+
+        let shape = Shape::infer(self, name)?;
+
+        let mut tokens = TokenStream::new();
+        shape.definitions(&mut HashMap::new(), &mut tokens)?;
+
+        let ident = format_ident!("{}", name.to_case(Case::UpperSnake));
+        let value_type = shape.type_tokens();
+        let expression = shape.expression(self)?;
+
+        tokens.extend(quote! {
+            pub static #ident: #value_type = #expression;
+        });
+
+        Ok(tokens)
+    }
+}
+
+impl ToTokens for Value {
+    /// Generates the value as `pub static DATA: Data`, see [`Value::to_static_tokens`].
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(self.to_static_tokens("data"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(entries: &[(&str, Value)]) -> Value {
+        Value::Map(
+            entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect(),
+        )
+    }
+
+    fn infer(value: &Value) -> Shape {
+        Shape::infer(value, "data").unwrap()
+    }
+
+    #[test]
+    fn infer_integers() {
+        assert_eq!(infer(&Value::Integer(1)), Shape::NonNegative);
+        assert_eq!(infer(&Value::Integer(-1)), Shape::Signed);
+        assert_eq!(infer(&Value::Unsigned(u64::MAX)), Shape::Unsigned);
+    }
+
+    #[test]
+    fn infer_maps() {
+        let shape = infer(&map(&[("type", Value::Bool(true))]));
+        assert_eq!(
+            shape,
+            Shape::Struct {
+                name: "data".to_string(),
+                fields: vec![("type_".to_string(), Shape::Bool)],
+            }
+        );
+
+        let shape = infer(&map(&[("a-b", Value::Bool(true))]));
+        assert_eq!(
+            shape,
+            Shape::Table {
+                name: "data".to_string(),
+                value: Box::new(Shape::Bool),
+            }
+        );
+    }
+
+    #[test]
+    fn unify_integers() {
+        let unify = |a: Shape, b: Shape| a.unify(b);
+
+        assert_eq!(
+            unify(Shape::NonNegative, Shape::Unsigned),
+            Ok(Shape::Unsigned)
+        );
+        assert_eq!(unify(Shape::Signed, Shape::NonNegative), Ok(Shape::Signed));
+        assert_eq!(unify(Shape::Unsigned, Shape::Float), Ok(Shape::Float));
+        assert!(unify(Shape::Signed, Shape::Unsigned).is_err());
+    }
+
+    #[test]
+    fn unify_optional_fields() {
+        let a = infer(&map(&[("x", Value::Integer(1))]));
+        let b = infer(&map(&[("y", Value::Null)]));
+
+        assert_eq!(
+            a.unify(b),
+            Ok(Shape::Struct {
+                name: "data".to_string(),
+                fields: vec![
+                    ("x".to_string(), Shape::Option(Box::new(Shape::NonNegative))),
+                    ("y".to_string(), Shape::Null),
+                ],
+            })
+        );
+        assert!(Shape::Str.unify(Shape::Bool).is_err());
+    }
+
+    #[test]
+    fn generate_struct() {
+        let value = map(&[("name", Value::String("a".to_string()))]);
+
+        let expected = quote! {
+            #[derive(Debug, Clone, Copy, PartialEq)]
+            pub struct Data {
+                pub name: &'static str,
+            }
+            pub static DATA: Data = Data { name: "a" };
+        };
+        assert_eq!(value.to_token_stream().to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn generate_unsigned_sequence() {
+        let value = Value::Sequence(vec![Value::Integer(1), Value::Unsigned(u64::MAX)]);
+
+        let expected = quote! {
+            pub static DATA: &'static [u64] = &[1u64, 18446744073709551615u64];
+        };
+        assert_eq!(value.to_token_stream().to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn generate_colliding_type_names() {
+        let value = map(&[
+            ("a_b", map(&[("x", Value::Integer(1))])),
+            ("a", map(&[("b", map(&[("y", Value::String("s".into()))]))])),
+        ]);
+
+        let tokens = value.to_token_stream().to_string();
+        assert!(tokens.starts_with("compile_error !"));
+        assert!(tokens.contains("`DataAB`"));
+    }
+
+    #[test]
+    fn generate_invalid_name() {
+        for name in ["1st", "", "self"] {
+            let tokens = Value::Integer(1).to_static_tokens(name).to_string();
+            assert!(tokens.starts_with("compile_error !"), "{name}: {tokens}");
+        }
+    }
+
+    #[test]
+    fn generate_shared_type_names() {
+        let value = map(&[
+            ("a_b", map(&[("x", Value::Integer(1))])),
+            ("a", map(&[("b", map(&[("x", Value::Integer(2))]))])),
+        ]);
+
+        let tokens = value.to_token_stream().to_string();
+        assert_eq!(tokens.matches("pub struct DataAB").count(), 1);
+    }
+}
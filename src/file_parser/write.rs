@@ -8,9 +8,13 @@ use std::{
 use proc_macro2::TokenStream;
 use quote::{ToTokens, quote};
 
-use crate::{FileParser, FileType, file_parser::ParseFrom, write_tokens_blocking};
+use crate::{
+    FileParser, FileType,
+    file_parser::{FileSource, ParseFrom},
+    write_tokens_blocking,
+};
 
-impl<State, T: ParseFrom<String, State> + ToTokens> FileParser<State, T> {
+impl<State, T: ParseFrom<S, State> + ToTokens, S: FileSource> FileParser<State, T, S> {
     /// Write the parsed files to some target or `stdout` if None.
     pub fn write(
         &self,
@@ -18,73 +22,85 @@ impl<State, T: ParseFrom<String, State> + ToTokens> FileParser<State, T> {
         generator_name: &'static str,
         generator_version: &'static str,
     ) -> Result<(), WriteError> {
-        match target {
-            Some(target) => {
-                if target.exists() {
-                    let metadata = target.metadata().map_err(|source| WriteError {
-                        kind: WriteErrorKind::ReadMetadata {
+        write_output(
+            self.to_token_stream(),
+            target,
+            generator_name,
+            generator_version,
+        )
+    }
+}
+
+/// Write tokens to some target file or `stdout` if None.
+pub(crate) fn write_output(
+    tokens: TokenStream,
+    target: Option<&Path>,
+    generator_name: &'static str,
+    generator_version: &'static str,
+) -> Result<(), WriteError> {
+    match target {
+        Some(target) => {
+            if target.exists() {
+                let metadata = target.metadata().map_err(|source| WriteError {
+                    kind: WriteErrorKind::ReadMetadata {
+                        path: target.to_path_buf(),
+                        source,
+                    },
+                })?;
+
+                let file_type = FileType::from(&metadata);
+                if file_type != FileType::File {
+                    return Err(WriteError {
+                        kind: WriteErrorKind::UnsupportedFileType {
+                            file_type,
                             path: target.to_path_buf(),
-                            source,
                         },
-                    })?;
-
-                    let file_type = FileType::from(&metadata);
-                    if file_type != FileType::File {
-                        return Err(WriteError {
-                            kind: WriteErrorKind::UnsupportedFileType {
-                                file_type,
-                                path: target.to_path_buf(),
-                            },
-                        });
-                    }
+                    });
                 }
+            }
 
-                let output_file = File::options()
-                    .write(true)
-                    .truncate(true)
-                    .create(true)
-                    .open(target)
-                    .map_err(|source| WriteError {
-                        kind: WriteErrorKind::OpenTarget {
-                            path: target.to_path_buf(),
-                            source,
-                        },
-                    })?;
-
-                write_tokens_blocking(
-                    self.to_token_stream(),
-                    output_file,
-                    generator_name,
-                    generator_version,
-                )
+            let output_file = File::options()
+                .write(true)
+                .truncate(true)
+                .create(true)
+                .open(target)
                 .map_err(|source| WriteError {
-                    kind: WriteErrorKind::RustFmt { source },
+                    kind: WriteErrorKind::OpenTarget {
+                        path: target.to_path_buf(),
+                        source,
+                    },
                 })?;
-            }
 
-            None => {
-                write_tokens_blocking(
-                    self.to_token_stream(),
-                    stdout(),
-                    generator_name,
-                    generator_version,
-                )
-                .map_err(|source| WriteError {
+            write_tokens_blocking(tokens, output_file, generator_name, generator_version).map_err(
+                |source| WriteError {
                     kind: WriteErrorKind::RustFmt { source },
-                })?;
-            }
+                },
+            )?;
         }
 
-        Ok(())
+        None => {
+            write_tokens_blocking(tokens, stdout(), generator_name, generator_version).map_err(
+                |source| WriteError {
+                    kind: WriteErrorKind::RustFmt { source },
+                },
+            )?;
+        }
     }
+
+    Ok(())
 }
 
-impl<State, T: ParseFrom<String, State> + ToTokens> ToTokens for FileParser<State, T> {
+impl<State, T: ParseFrom<S, State> + ToTokens, S: FileSource> ToTokens for FileParser<State, T, S> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let modules = &self.modules;
+        let index = self
+            .index
+            .as_ref()
+            .map(|index| index.to_tokens(&self.modules));
 
         let new_tokens = quote! {
             #( #modules )*
+            #index
         };
         tokens.extend(new_tokens);
     }
@@ -139,6 +155,15 @@ pub enum WriteErrorKind {
         source: io::Error,
     },
 
+    #[non_exhaustive]
+    /// Resolving the directory of the target failed.
+    ResolveTarget {
+        /// The target file path.
+        path: PathBuf,
+        /// The source IO error.
+        source: io::Error,
+    },
+
     #[non_exhaustive]
     /// `rustfmt` failed to be spawned or format the output.
     RustFmt {
@@ -165,6 +190,11 @@ impl fmt::Display for WriteErrorKind {
                 "failed to read the metadata of the target file `{}`",
                 path.to_string_lossy()
             ),
+            Self::ResolveTarget { path, .. } => write!(
+                f,
+                "failed to resolve the directory of the target file `{}`",
+                path.to_string_lossy()
+            ),
             Self::RustFmt { .. } => write!(f, "running `rustfmt` on the output failed"),
         }
     }
@@ -174,6 +204,7 @@ impl Error for WriteErrorKind {
         match &self {
             Self::OpenTarget { source, .. } => Some(source),
             Self::ReadMetadata { source, .. } => Some(source),
+            Self::ResolveTarget { source, .. } => Some(source),
             Self::RustFmt { source, .. } => Some(source),
             _ => None,
         }
@@ -5,16 +5,44 @@ mod action;
 mod file_parser;
 mod file_type;
 mod log;
+mod output;
+mod prompt;
+mod session;
 #[cfg(feature = "generate")]
 mod write_tokens;
 
-pub use action::{Action, ActionResult, State};
+pub use action::{
+    Action, ActionErrorResult, ActionFailureResult, ActionFuture, ActionMessageResult,
+    ActionResult, ActionSkipResult, ErrorDetail, MultiAction, RunOptions, State,
+};
 pub use file_parser::{
-    FileParser, Module, ParseFileError, ParseFileErrorKind, ParseFrom, to_valid_ident,
+    ConflictPolicy, FileParser, FileSource, Module, ModuleConflictError, ParseFileError,
+    ParseFileErrorKind, ParseFrom, ParseOptions, SymlinkPolicy, to_valid_ident,
 };
 pub use file_type::FileType;
-pub use log::{print_error, print_fail, print_success, print_warning};
+pub use log::{
+    print_debug, print_error, print_fail, print_info, print_success, print_trace, print_warning,
+};
+pub use output::{
+    MemorySink, OutputChoice, OutputFormat, ParseOutputFormatError, Sink, StdSink, Stream,
+    Verbosity, format, reset_sink, set_color, set_cursor_control, set_format, set_sink,
+    set_verbosity, verbosity,
+};
+pub use prompt::{PromptError, PromptErrorKind, confirm, password, select, set_assume_yes, text};
+pub use session::{ActionRecord, Session, SessionSummary};
 
+#[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+pub use file_parser::DeserializeError;
+#[cfg(feature = "json")]
+pub use file_parser::Json;
+#[cfg(feature = "toml")]
+pub use file_parser::Toml;
+#[cfg(feature = "generate-data")]
+pub use file_parser::Value;
+#[cfg(feature = "yaml")]
+pub use file_parser::Yaml;
+#[cfg(feature = "generate")]
+pub use file_parser::{Asset, Assets, Dispatch, Index};
 #[cfg(feature = "generate")]
 pub use file_parser::{WriteError, WriteErrorKind};
 #[cfg(feature = "generate")]
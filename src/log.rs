@@ -1,31 +1,134 @@
 use core::fmt::Display;
+use std::time::SystemTime;
 
 use ts_rust_helper::style::*;
 
-/// Print an error message with the format:
+use crate::output::{self, JsonLine, OutputFormat, Stream, Verbosity};
+
+/// Print an error message to `stderr` with the format:
 ///
 /// `error: {message}
 pub fn print_error<S: Display>(message: S) {
-    println!("{BOLD}{RED}error{RESET}{BOLD}:{RESET} {message}");
+    print_message(
+        Stream::Stderr,
+        "error",
+        &format!("{BOLD}{RED}error{RESET}"),
+        &message,
+    );
 }
 
-/// Print a warning message with the format:
+/// Print a warning message to `stderr` with the format:
 ///
 /// `warning: {message}`
+///
+/// Suppressed in [`Verbosity::Quiet`].
 pub fn print_warning<S: Display>(message: S) {
-    println!("{BOLD}{YELLOW}warning{RESET}{BOLD}:{RESET} {message}");
+    if !output::enabled(Verbosity::Normal) {
+        return;
+    }
+
+    print_message(
+        Stream::Stderr,
+        "warning",
+        &format!("{BOLD}{YELLOW}warning{RESET}"),
+        &message,
+    );
 }
 
-/// Print a success message with the format:
+/// Print a success message to `stdout` with the format:
 ///
 /// `Success: {message}`
+///
+/// Suppressed in [`Verbosity::Quiet`].
 pub fn print_success<S: Display>(message: S) {
-    println!("{BOLD}{GREEN}Success{RESET}{BOLD}:{RESET} {message}");
+    if !output::enabled(Verbosity::Normal) {
+        return;
+    }
+
+    print_message(
+        Stream::Stdout,
+        "success",
+        &format!("{BOLD}{GREEN}Success{RESET}"),
+        &message,
+    );
 }
 
-/// Print a failure message with the format:
+/// Print a failure message to `stderr` with the format:
 ///
 /// `Fail: {message}`
 pub fn print_fail<S: Display>(message: S) {
-    println!("{BOLD}{RED}Fail{RESET}{BOLD}:{RESET} {message}");
+    print_message(
+        Stream::Stderr,
+        "fail",
+        &format!("{BOLD}{RED}Fail{RESET}"),
+        &message,
+    );
+}
+
+/// Print an info message to `stdout` with the format:
+///
+/// `info: {message}`
+///
+/// Suppressed in [`Verbosity::Quiet`].
+pub fn print_info<S: Display>(message: S) {
+    if !output::enabled(Verbosity::Normal) {
+        return;
+    }
+
+    print_message(
+        Stream::Stdout,
+        "info",
+        &format!("{BOLD}{BLUE}info{RESET}"),
+        &message,
+    );
+}
+
+/// Print a debug message to `stdout` with the format:
+///
+/// `debug: {message}`
+///
+/// Only printed in [`Verbosity::Verbose`] or higher.
+pub fn print_debug<S: Display>(message: S) {
+    if !output::enabled(Verbosity::Verbose) {
+        return;
+    }
+
+    print_message(
+        Stream::Stdout,
+        "debug",
+        &format!("{BOLD}{MAGENTA}debug{RESET}"),
+        &message,
+    );
+}
+
+/// Print a trace message to `stdout` with the format:
+///
+/// `trace: {message}`
+///
+/// Only printed in [`Verbosity::Trace`].
+pub fn print_trace<S: Display>(message: S) {
+    if !output::enabled(Verbosity::Trace) {
+        return;
+    }
+
+    print_message(
+        Stream::Stdout,
+        "trace",
+        &format!("{BOLD}{DIM}trace{RESET}"),
+        &message,
+    );
+}
+
+/// Print a message in the current output format.
+fn print_message(stream: Stream, level: &str, styled_level: &str, message: &dyn Display) {
+    let line = match output::format() {
+        OutputFormat::Human => format!("{styled_level}{BOLD}:{RESET} {message}\n"),
+        OutputFormat::Json => JsonLine::new("log")
+            .string("level", level)
+            .string("message", &message.to_string())
+            .timestamp("timestamp", SystemTime::now())
+            .finish(),
+    };
+
+    output::write(stream, &line);
 }
@@ -0,0 +1,85 @@
+use core::{
+    error::Error,
+    fmt,
+    str::FromStr,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+/// The format output is written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OutputFormat {
+    /// Styled human readable text.
+    #[default]
+    Human,
+    /// One JSON object per line for each log message and action state.
+    Json,
+}
+
+impl OutputFormat {
+    const fn to_u8(self) -> u8 {
+        match self {
+            Self::Human => 0,
+            Self::Json => 1,
+        }
+    }
+
+    const fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Json,
+            _ => Self::Human,
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = ParseOutputFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "human" | "text" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(ParseOutputFormatError {
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Human => write!(f, "human"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// The value is not a known output format.
+pub struct ParseOutputFormatError {
+    /// The value that failed to parse.
+    pub value: String,
+}
+impl fmt::Display for ParseOutputFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown output format `{}`, expected `human` or `json`",
+            self.value
+        )
+    }
+}
+impl Error for ParseOutputFormatError {}
+
+static FORMAT: AtomicU8 = AtomicU8::new(OutputFormat::Human.to_u8());
+
+/// Set the global output format, e.g., from a parsed `--format json` flag.
+pub fn set_format(format: OutputFormat) {
+    FORMAT.store(format.to_u8(), Ordering::Relaxed);
+}
+
+/// Get the global output format.
+pub fn format() -> OutputFormat {
+    OutputFormat::from_u8(FORMAT.load(Ordering::Relaxed))
+}
@@ -0,0 +1,89 @@
+use core::fmt::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Builder for a single line JSON object.
+pub(crate) struct JsonLine {
+    output: String,
+}
+
+impl JsonLine {
+    /// Create a JSON line for an event type.
+    pub(crate) fn new(event: &str) -> Self {
+        let mut line = Self {
+            output: String::from("{"),
+        };
+        line.key("type");
+        line.push_string(event);
+        line
+    }
+
+    /// Add a string field.
+    pub(crate) fn string(mut self, key: &str, value: &str) -> Self {
+        self.key(key);
+        self.push_string(value);
+        self
+    }
+
+    /// Add an array of strings field.
+    pub(crate) fn strings(mut self, key: &str, values: &[String]) -> Self {
+        self.key(key);
+        self.output.push('[');
+        for (index, value) in values.iter().enumerate() {
+            if index > 0 {
+                self.output.push(',');
+            }
+            self.push_string(value);
+        }
+        self.output.push(']');
+        self
+    }
+
+    /// Add an unsigned number field.
+    pub(crate) fn number<N: Into<u128>>(mut self, key: &str, value: N) -> Self {
+        self.key(key);
+        let _ = write!(self.output, "{}", value.into());
+        self
+    }
+
+    /// Add a timestamp field as milliseconds since the Unix epoch.
+    pub(crate) fn timestamp(self, key: &str, time: SystemTime) -> Self {
+        let millis = time
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis())
+            .unwrap_or_default();
+
+        self.number(key, millis)
+    }
+
+    /// Finish the object, returning it terminated with a new line.
+    pub(crate) fn finish(mut self) -> String {
+        self.output.push_str("}\n");
+        self.output
+    }
+
+    fn key(&mut self, key: &str) {
+        if self.output.len() > 1 {
+            self.output.push(',');
+        }
+        self.push_string(key);
+        self.output.push(':');
+    }
+
+    fn push_string(&mut self, value: &str) {
+        self.output.push('"');
+        for character in value.chars() {
+            match character {
+                '"' => self.output.push_str("\\\""),
+                '\\' => self.output.push_str("\\\\"),
+                '\n' => self.output.push_str("\\n"),
+                '\r' => self.output.push_str("\\r"),
+                '\t' => self.output.push_str("\\t"),
+                character if character.is_control() => {
+                    let _ = write!(self.output, "\\u{:04x}", u32::from(character));
+                }
+                character => self.output.push(character),
+            }
+        }
+        self.output.push('"');
+    }
+}
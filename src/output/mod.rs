@@ -0,0 +1,185 @@
+mod format;
+mod json;
+mod sink;
+mod terminal;
+mod verbosity;
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use ts_rust_helper::style::ERASE_LINE_UP;
+
+pub use format::{OutputFormat, ParseOutputFormatError, format, set_format};
+pub use sink::{MemorySink, Sink, StdSink, Stream};
+pub use terminal::{OutputChoice, set_color, set_cursor_control};
+pub use verbosity::{Verbosity, set_verbosity, verbosity};
+
+pub(crate) use json::JsonLine;
+pub(crate) use verbosity::enabled;
+
+/// The global output state.
+struct Output {
+    /// The sink all output is written to, `None` uses the default [`StdSink`].
+    sink: Option<Box<dyn Sink>>,
+    /// The owner of the last line written, if it was written by an owner.
+    last_line_owner: Option<u64>,
+    /// Lines kept at the bottom of `stdout`, other output is written above them.
+    pinned: Option<Pinned>,
+}
+
+/// Lines kept at the bottom of `stdout` by an owner.
+struct Pinned {
+    owner: u64,
+    output: String,
+    lines: usize,
+}
+
+static OUTPUT: Mutex<Output> = Mutex::new(Output {
+    sink: None,
+    last_line_owner: None,
+    pinned: None,
+});
+
+impl Output {
+    fn lock() -> MutexGuard<'static, Self> {
+        OUTPUT.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Write the output to the sink, stripping styles if the stream is not colored.
+    fn write(&mut self, stream: Stream, output: &str) {
+        let sink: &mut dyn Sink = match self.sink.as_mut() {
+            Some(sink) => sink.as_mut(),
+            None => &mut StdSink::new(),
+        };
+
+        let _ = if terminal::color_enabled(sink.is_terminal(stream)) {
+            sink.write(stream, output)
+        } else {
+            sink.write(stream, &terminal::strip_styles(output))
+        };
+    }
+
+    /// Write the output above any pinned lines, the pinned lines will own the last line.
+    fn write_above_pinned(&mut self, stream: Stream, output: &str) {
+        match self.pinned.take() {
+            Some(pinned) => {
+                self.write(Stream::Stdout, &ERASE_LINE_UP.repeat(pinned.lines));
+                self.write(stream, output);
+                self.write(Stream::Stdout, &pinned.output);
+                self.last_line_owner = Some(pinned.owner);
+                self.pinned = Some(pinned);
+            }
+            None => {
+                self.write(stream, output);
+                self.last_line_owner = None;
+            }
+        }
+    }
+
+    fn is_terminal(&self, stream: Stream) -> bool {
+        match self.sink.as_ref() {
+            Some(sink) => sink.is_terminal(stream),
+            None => StdSink::new().is_terminal(stream),
+        }
+    }
+}
+
+/// Set the sink that all log and action output is written to.
+pub fn set_sink<S: Sink + 'static>(sink: S) {
+    let mut state = Output::lock();
+    state.sink = Some(Box::new(sink));
+    state.last_line_owner = None;
+    state.pinned = None;
+}
+
+/// Reset the output sink back to the default [`StdSink`].
+pub fn reset_sink() {
+    let mut state = Output::lock();
+    state.sink = None;
+    state.last_line_owner = None;
+    state.pinned = None;
+}
+
+/// Write some output to a stream of the current sink.
+///
+/// The output is written while holding the output lock, so output written in a single call will
+/// not be interleaved with output from other threads. Any styles are removed if the stream
+/// should not be colored. Failures to write are ignored.
+pub(crate) fn write(stream: Stream, output: &str) {
+    Output::lock().write_above_pinned(stream, output);
+}
+
+/// Write a line on behalf of some owner.
+///
+/// If `overwrite` is set, the owner wrote the last line, and the stream supports cursor control,
+/// then the last line is replaced, otherwise the line is written below it.
+pub(crate) fn write_line(stream: Stream, owner: u64, overwrite: bool, line: &str) {
+    let mut state = Output::lock();
+
+    if state.pinned.is_some() {
+        state.write_above_pinned(stream, &format!("{line}\n"));
+        return;
+    }
+
+    let erase = overwrite
+        && state.last_line_owner == Some(owner)
+        && terminal::cursor_control_enabled(state.is_terminal(stream));
+
+    if erase {
+        state.write(stream, &format!("{ERASE_LINE_UP}{line}\n"));
+    } else {
+        state.write(stream, &format!("{line}\n"));
+    }
+
+    state.last_line_owner = Some(owner);
+}
+
+/// Redraw the last line if it was written by the owner and the stream supports cursor control.
+///
+/// Returns if the line was redrawn.
+pub(crate) fn redraw_line(stream: Stream, owner: u64, line: &str) -> bool {
+    let mut state = Output::lock();
+
+    let can_redraw = state.last_line_owner == Some(owner)
+        && terminal::cursor_control_enabled(state.is_terminal(stream));
+
+    if can_redraw {
+        state.write(stream, &format!("{ERASE_LINE_UP}{line}\n"));
+    }
+
+    can_redraw
+}
+
+/// Returns if output to the stream may use cursor control.
+pub(crate) fn cursor_control_enabled(stream: Stream) -> bool {
+    let state = Output::lock();
+    terminal::cursor_control_enabled(state.is_terminal(stream))
+}
+
+/// Redraw the lines pinned to the bottom of `stdout` by the owner, writing the completed output
+/// above them.
+///
+/// Should only be used if `stdout` supports cursor control.
+pub(crate) fn redraw_pinned(owner: u64, completed: &str, pinned: &str, pinned_lines: usize) {
+    let mut state = Output::lock();
+
+    // Lines pinned by another owner are left in place.
+    let previous_lines = match state.pinned.take() {
+        Some(previous) if previous.owner == owner => previous.lines,
+        _ => 0,
+    };
+
+    state.write(
+        Stream::Stdout,
+        &format!(
+            "{}{completed}{pinned}",
+            ERASE_LINE_UP.repeat(previous_lines)
+        ),
+    );
+
+    state.last_line_owner = Some(owner);
+    state.pinned = (pinned_lines > 0).then(|| Pinned {
+        owner,
+        output: pinned.to_string(),
+        lines: pinned_lines,
+    });
+}
@@ -0,0 +1,124 @@
+use std::{
+    io::{self, IsTerminal, Write, stderr, stdout},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+/// The stream some output is written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stream {
+    /// Standard output, used for progress and success messages.
+    Stdout,
+    /// Standard error, used for errors and warnings.
+    Stderr,
+}
+
+/// A destination for log and action output.
+pub trait Sink: Send {
+    /// Write the output to the stream.
+    fn write(&mut self, stream: Stream, output: &str) -> io::Result<()>;
+
+    /// Returns if the stream is an interactive terminal.
+    fn is_terminal(&self, stream: Stream) -> bool {
+        let _ = stream;
+        false
+    }
+}
+
+/// Sink that writes to the process `stdout` and `stderr`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdSink {
+    merged: bool,
+}
+
+impl StdSink {
+    /// Create a sink that writes each stream to its matching standard stream.
+    pub fn new() -> Self {
+        Self { merged: false }
+    }
+
+    /// Create a sink that writes both streams to `stdout`.
+    pub fn merged() -> Self {
+        Self { merged: true }
+    }
+}
+
+impl Sink for StdSink {
+    fn write(&mut self, stream: Stream, output: &str) -> io::Result<()> {
+        match stream {
+            Stream::Stderr if !self.merged => {
+                let mut stderr = stderr().lock();
+                stderr.write_all(output.as_bytes())?;
+                stderr.flush()
+            }
+            _ => {
+                let mut stdout = stdout().lock();
+                stdout.write_all(output.as_bytes())?;
+                stdout.flush()
+            }
+        }
+    }
+
+    fn is_terminal(&self, stream: Stream) -> bool {
+        match stream {
+            Stream::Stderr if !self.merged => stderr().is_terminal(),
+            _ => stdout().is_terminal(),
+        }
+    }
+}
+
+/// Sink that captures the output in memory, clones share the same buffers.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySink {
+    buffers: Arc<Mutex<MemoryBuffers>>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryBuffers {
+    stdout: String,
+    stderr: String,
+}
+
+impl MemorySink {
+    /// Create a new empty memory sink.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The output written to `stdout`.
+    pub fn stdout(&self) -> String {
+        self.buffers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .stdout
+            .clone()
+    }
+
+    /// The output written to `stderr`.
+    pub fn stderr(&self) -> String {
+        self.buffers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .stderr
+            .clone()
+    }
+
+    /// Clear the captured output.
+    pub fn clear(&self) {
+        let mut buffers = self.buffers.lock().unwrap_or_else(PoisonError::into_inner);
+        buffers.stdout.clear();
+        buffers.stderr.clear();
+    }
+}
+
+impl Sink for MemorySink {
+    fn write(&mut self, stream: Stream, output: &str) -> io::Result<()> {
+        let mut buffers = self.buffers.lock().unwrap_or_else(PoisonError::into_inner);
+
+        match stream {
+            Stream::Stdout => buffers.stdout.push_str(output),
+            Stream::Stderr => buffers.stderr.push_str(output),
+        }
+
+        Ok(())
+    }
+}
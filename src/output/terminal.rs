@@ -0,0 +1,97 @@
+use core::sync::atomic::{AtomicU8, Ordering};
+use std::{env, sync::LazyLock};
+
+use regex::Regex;
+
+/// Override for automatic terminal feature detection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OutputChoice {
+    /// Detect if the feature is supported from the environment and the output stream.
+    #[default]
+    Auto,
+    /// Always use the feature.
+    Always,
+    /// Never use the feature.
+    Never,
+}
+
+impl OutputChoice {
+    const fn to_u8(self) -> u8 {
+        match self {
+            Self::Auto => 0,
+            Self::Always => 1,
+            Self::Never => 2,
+        }
+    }
+
+    const fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Always,
+            2 => Self::Never,
+            _ => Self::Auto,
+        }
+    }
+}
+
+static COLOR: AtomicU8 = AtomicU8::new(OutputChoice::Auto.to_u8());
+static CURSOR_CONTROL: AtomicU8 = AtomicU8::new(OutputChoice::Auto.to_u8());
+
+/// Set if output should be colored.
+///
+/// When [`OutputChoice::Auto`], `NO_COLOR`, `CLICOLOR_FORCE`, `CLICOLOR`, `TERM=dumb`, and if the
+/// stream is a terminal are respected.
+pub fn set_color(choice: OutputChoice) {
+    COLOR.store(choice.to_u8(), Ordering::Relaxed);
+}
+
+/// Set if output may use cursor control to overwrite previous lines.
+///
+/// When [`OutputChoice::Auto`], `TERM=dumb` and if the stream is a terminal are respected.
+pub fn set_cursor_control(choice: OutputChoice) {
+    CURSOR_CONTROL.store(choice.to_u8(), Ordering::Relaxed);
+}
+
+/// Returns if an environment variable is set to a non-empty value.
+fn env_set(key: &str) -> bool {
+    env::var_os(key).is_some_and(|value| !value.is_empty())
+}
+
+/// Returns if an environment variable is set to the value.
+fn env_is(key: &str, expected: &str) -> bool {
+    env::var_os(key).is_some_and(|value| value == expected)
+}
+
+/// Returns if output to a stream should be colored.
+pub(crate) fn color_enabled(is_terminal: bool) -> bool {
+    match OutputChoice::from_u8(COLOR.load(Ordering::Relaxed)) {
+        OutputChoice::Always => true,
+        OutputChoice::Never => false,
+        OutputChoice::Auto => {
+            if env_set("NO_COLOR") {
+                false
+            } else if env_set("CLICOLOR_FORCE") && !env_is("CLICOLOR_FORCE", "0") {
+                true
+            } else if env_is("CLICOLOR", "0") || env_is("TERM", "dumb") {
+                false
+            } else {
+                is_terminal
+            }
+        }
+    }
+}
+
+/// Returns if output to a stream may use cursor control.
+pub(crate) fn cursor_control_enabled(is_terminal: bool) -> bool {
+    match OutputChoice::from_u8(CURSOR_CONTROL.load(Ordering::Relaxed)) {
+        OutputChoice::Always => true,
+        OutputChoice::Never => false,
+        OutputChoice::Auto => is_terminal && !env_is("TERM", "dumb"),
+    }
+}
+
+/// Remove any ANSI style escape sequences from some output, cursor control sequences are kept.
+pub(crate) fn strip_styles(output: &str) -> String {
+    static STYLES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\x1b\[[0-9;]*m").unwrap());
+
+    STYLES.replace_all(output, "").into_owned()
+}
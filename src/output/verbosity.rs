@@ -0,0 +1,65 @@
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// How much output should be printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Verbosity {
+    /// Only errors and failures are printed, `-q`.
+    Quiet,
+    /// Errors, warnings, info, and action progress are printed.
+    #[default]
+    Normal,
+    /// Debug messages are also printed, `-v`.
+    Verbose,
+    /// Trace messages are also printed, `-vv`.
+    Trace,
+}
+
+impl Verbosity {
+    /// Get the verbosity from the `-q` flag and the number of `-v` flags, `quiet` takes precedence.
+    pub fn from_flags(quiet: bool, verbose: u8) -> Self {
+        if quiet {
+            return Self::Quiet;
+        }
+
+        match verbose {
+            0 => Self::Normal,
+            1 => Self::Verbose,
+            _ => Self::Trace,
+        }
+    }
+
+    const fn to_u8(self) -> u8 {
+        match self {
+            Self::Quiet => 0,
+            Self::Normal => 1,
+            Self::Verbose => 2,
+            Self::Trace => 3,
+        }
+    }
+
+    const fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Quiet,
+            2 => Self::Verbose,
+            3 => Self::Trace,
+            _ => Self::Normal,
+        }
+    }
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal.to_u8());
+
+/// Set the global verbosity.
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity.to_u8(), Ordering::Relaxed);
+}
+
+/// Get the global verbosity.
+pub fn verbosity() -> Verbosity {
+    Verbosity::from_u8(VERBOSITY.load(Ordering::Relaxed))
+}
+
+/// Returns if output requiring some verbosity should be printed.
+pub(crate) fn enabled(required: Verbosity) -> bool {
+    verbosity() >= required
+}
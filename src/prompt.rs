@@ -0,0 +1,281 @@
+use core::{
+    error::Error,
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use std::io::{self, BufRead, IsTerminal, stdin};
+
+use ts_rust_helper::style::*;
+
+use crate::{
+    output::{self, Stream},
+    print_error,
+};
+
+/// If prompts should be answered without asking, e.g., from a `--yes` flag.
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+/// Set if prompts should be answered without asking, e.g., from a `--yes` flag.
+///
+/// Confirmations are answered yes, other prompts use their default.
+pub fn set_assume_yes(assume_yes: bool) {
+    ASSUME_YES.store(assume_yes, Ordering::Relaxed);
+}
+
+/// Returns if prompts should ask the user.
+fn is_interactive() -> bool {
+    !ASSUME_YES.load(Ordering::Relaxed) && stdin().is_terminal()
+}
+
+/// Print the question and read a trimmed line of input, `None` if the input has ended.
+fn ask(question: &str, hint: &str) -> Result<Option<String>, PromptError> {
+    Ok(read_answer(question, hint)?.map(|answer| answer.trim().to_string()))
+}
+
+/// Print the question and read a line of input without the line ending, `None` if the input has
+/// ended.
+fn read_answer(question: &str, hint: &str) -> Result<Option<String>, PromptError> {
+    output::write(
+        Stream::Stderr,
+        &format!("{BOLD}{CYAN}?{RESET} {BOLD}{question}{RESET} {DIM}{hint}{RESET} "),
+    );
+
+    let mut input = String::new();
+    let read = stdin()
+        .lock()
+        .read_line(&mut input)
+        .map_err(|source| PromptError {
+            kind: PromptErrorKind::ReadInput { source },
+        })?;
+
+    if read == 0 {
+        output::write(Stream::Stderr, "\n");
+        return Ok(None);
+    }
+
+    let line = input
+        .strip_suffix('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .unwrap_or(&input);
+
+    Ok(Some(line.to_string()))
+}
+
+/// Ask a yes/no question.
+///
+/// If stdin is not a terminal, the default is used. If yes is assumed, the answer is yes.
+pub fn confirm(question: &str, default: bool) -> Result<bool, PromptError> {
+    if ASSUME_YES.load(Ordering::Relaxed) {
+        return Ok(true);
+    }
+    if !is_interactive() {
+        return Ok(default);
+    }
+
+    let hint = if default { "[Y/n]" } else { "[y/N]" };
+
+    loop {
+        let Some(answer) = ask(question, hint)? else {
+            return Ok(default);
+        };
+
+        match answer.to_ascii_lowercase().as_str() {
+            "" => return Ok(default),
+            "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            _ => print_error("answer `y` or `n`"),
+        }
+    }
+}
+
+/// Ask for free text, asking again until the answer passes validation.
+///
+/// If stdin is not a terminal or yes is assumed, the default is used without validation, the
+/// caller is trusted to pass a valid default.
+pub fn text<V>(question: &str, default: Option<&str>, validate: V) -> Result<String, PromptError>
+where
+    V: Fn(&str) -> Result<(), String>,
+{
+    if !is_interactive() {
+        return default.map(str::to_string).ok_or(PromptError {
+            kind: PromptErrorKind::NoDefault,
+        });
+    }
+
+    let hint = default
+        .map(|default| format!("({default})"))
+        .unwrap_or_default();
+
+    loop {
+        let answer = match ask(question, &hint)? {
+            Some(answer) if answer.is_empty() => default.map(str::to_string).unwrap_or(answer),
+            Some(answer) => answer,
+            None => {
+                return default.map(str::to_string).ok_or(PromptError {
+                    kind: PromptErrorKind::NoDefault,
+                });
+            }
+        };
+
+        match validate(&answer) {
+            Ok(()) => return Ok(answer),
+            Err(message) => print_error(message),
+        }
+    }
+}
+
+/// Ask to select one of the options, returning the index of the selected option.
+///
+/// If stdin is not a terminal or yes is assumed, the default index is used.
+///
+/// Fails if there are no options or the default is not the index of an option.
+pub fn select<S: fmt::Display>(
+    question: &str,
+    options: &[S],
+    default: usize,
+) -> Result<usize, PromptError> {
+    if options.is_empty() {
+        return Err(PromptError {
+            kind: PromptErrorKind::NoOptions,
+        });
+    }
+    if default >= options.len() {
+        return Err(PromptError {
+            kind: PromptErrorKind::InvalidDefault {
+                default,
+                options: options.len(),
+            },
+        });
+    }
+    if !is_interactive() {
+        return Ok(default);
+    }
+
+    let options_list: String = options
+        .iter()
+        .enumerate()
+        .map(|(index, option)| format!("  {BOLD}{}{RESET}) {option}\n", index + 1))
+        .collect();
+    let hint = format!("[1-{}] ({})", options.len(), default + 1);
+
+    loop {
+        output::write(Stream::Stderr, &options_list);
+
+        let Some(answer) = ask(question, &hint)? else {
+            return Ok(default);
+        };
+
+        if answer.is_empty() {
+            return Ok(default);
+        }
+
+        match answer.parse::<usize>() {
+            Ok(number) if (1..=options.len()).contains(&number) => return Ok(number - 1),
+            _ => print_error(format!("answer a number from 1 to {}", options.len())),
+        }
+    }
+}
+
+/// Ask for a password, the input is not echoed on Unix.
+///
+/// Only the line ending is removed, surrounding whitespace is kept as part of the password.
+///
+/// Fails if stdin is not a terminal or yes is assumed, as there is no default.
+pub fn password(question: &str) -> Result<String, PromptError> {
+    if !is_interactive() {
+        return Err(PromptError {
+            kind: PromptErrorKind::NoDefault,
+        });
+    }
+
+    set_echo(false);
+    let answer = read_answer(question, "");
+    set_echo(true);
+    output::write(Stream::Stderr, "\n");
+
+    Ok(answer?.unwrap_or_default())
+}
+
+/// Enable or disable the terminal echoing input.
+#[cfg(unix)]
+fn set_echo(echo: bool) {
+    let _ = std::process::Command::new("stty")
+        .arg(if echo { "echo" } else { "-echo" })
+        .stdin(std::process::Stdio::inherit())
+        .status();
+}
+
+/// Enable or disable the terminal echoing input.
+#[cfg(not(unix))]
+fn set_echo(_echo: bool) {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// Failed to prompt the user.
+pub struct PromptError {
+    /// The error variants.
+    pub kind: PromptErrorKind,
+}
+impl fmt::Display for PromptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error while prompting for input")
+    }
+}
+impl Error for PromptError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.kind)
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// The variants for failing to prompt the user.
+pub enum PromptErrorKind {
+    #[non_exhaustive]
+    /// Failed to read the input.
+    ReadInput {
+        /// The source IO error.
+        source: io::Error,
+    },
+
+    #[non_exhaustive]
+    /// The prompt could not ask the user and there is no default.
+    NoDefault,
+
+    #[non_exhaustive]
+    /// There were no options to select from.
+    NoOptions,
+
+    #[non_exhaustive]
+    /// The default is not the index of an option.
+    InvalidDefault {
+        /// The default index.
+        default: usize,
+        /// The number of options.
+        options: usize,
+    },
+}
+impl fmt::Display for PromptErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Self::ReadInput { .. } => write!(f, "failed reading the input"),
+            Self::NoDefault => write!(
+                f,
+                "the prompt is not interactive and there is no default answer"
+            ),
+            Self::NoOptions => write!(f, "there are no options to select from"),
+            Self::InvalidDefault { default, options } => write!(
+                f,
+                "the default option {default} is out of range for {options} options"
+            ),
+        }
+    }
+}
+impl Error for PromptErrorKind {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self {
+            Self::ReadInput { source } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use core::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use std::{
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::Instant,
+};
+
+use crate::{State, action::format_duration, print_error, print_fail, print_success};
+
+/// A record of a completed action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionRecord {
+    /// The state the action completed with.
+    pub state: State,
+    /// Verb for the in progress action.
+    pub actioning_verb: String,
+    /// Verb for the completed action.
+    pub actioned_verb: String,
+    /// Details for the action.
+    pub detail: String,
+    /// The recorded error message, if the action errored with one.
+    pub error: Option<String>,
+    /// The time the action took.
+    pub elapsed: Duration,
+
+    id: u64,
+}
+
+impl ActionRecord {
+    pub(crate) fn new(
+        id: u64,
+        state: State,
+        actioning_verb: String,
+        actioned_verb: String,
+        detail: String,
+        error: Option<String>,
+        elapsed: Duration,
+    ) -> Self {
+        Self {
+            state,
+            actioning_verb,
+            actioned_verb,
+            detail,
+            error,
+            elapsed,
+            id,
+        }
+    }
+}
+
+/// The counts of completed actions in a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SessionSummary {
+    /// The number of successful actions.
+    pub successes: usize,
+    /// The number of actions that completed with warnings.
+    pub warnings: usize,
+    /// The number of actions that errored.
+    pub errors: usize,
+    /// The number of skipped actions.
+    pub skipped: usize,
+    /// The number of cancelled actions.
+    pub cancelled: usize,
+    /// The time since the session started.
+    pub elapsed: Duration,
+}
+
+impl SessionSummary {
+    /// The total number of completed actions.
+    pub fn total(&self) -> usize {
+        self.successes + self.warnings + self.errors + self.skipped + self.cancelled
+    }
+}
+
+struct Recording {
+    /// The generation of the session that owns this recording.
+    generation: u64,
+    start: Instant,
+    records: Vec<ActionRecord>,
+}
+
+impl Recording {
+    fn summary(&self) -> SessionSummary {
+        let mut summary = SessionSummary {
+            elapsed: self.start.elapsed(),
+            ..Default::default()
+        };
+
+        for record in &self.records {
+            match record.state {
+                State::Success => summary.successes += 1,
+                State::Warning => summary.warnings += 1,
+                State::Error => summary.errors += 1,
+                State::Skipped => summary.skipped += 1,
+                State::Cancelled => summary.cancelled += 1,
+                State::InProgress => {}
+            }
+        }
+
+        summary
+    }
+
+    /// Print the failed actions and the summary, and return the summary.
+    fn print_summary(&self) -> SessionSummary {
+        let summary = self.summary();
+
+        for record in self
+            .records
+            .iter()
+            .filter(|record| record.state == State::Error)
+        {
+            let actioning = &record.actioning_verb;
+            let detail = &record.detail;
+
+            match &record.error {
+                Some(error) => print_error(format!("{actioning} {detail} failed: {error}")),
+                None => print_error(format!("{actioning} {detail} failed")),
+            }
+        }
+
+        let mut counts = vec![];
+        if summary.warnings > 0 {
+            counts.push(format!("{} with warnings", summary.warnings));
+        }
+        if summary.skipped > 0 {
+            counts.push(format!("{} skipped", summary.skipped));
+        }
+        if summary.cancelled > 0 {
+            counts.push(format!("{} cancelled", summary.cancelled));
+        }
+        let counts = if counts.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", counts.join(", "))
+        };
+
+        let total = summary.total();
+        let elapsed = format_duration(summary.elapsed);
+
+        if summary.errors == 0 {
+            print_success(format!("{total} actions completed in {elapsed}{counts}"));
+        } else {
+            print_fail(format!(
+                "{} of {total} actions failed in {elapsed}{counts}",
+                summary.errors
+            ));
+        }
+
+        summary
+    }
+}
+
+/// The active session recording, if any.
+static RECORDING: Mutex<Option<Recording>> = Mutex::new(None);
+
+/// The generation of the next session started.
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn lock() -> MutexGuard<'static, Option<Recording>> {
+    RECORDING.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Record a completed action in the active session, replacing any previous record of the action.
+pub(crate) fn record<F: FnOnce() -> ActionRecord>(record: F) {
+    let mut recording = lock();
+    let Some(recording) = recording.as_mut() else {
+        return;
+    };
+
+    let record = record();
+    match recording
+        .records
+        .iter_mut()
+        .find(|existing| existing.id == record.id)
+    {
+        Some(existing) => *existing = record,
+        None => recording.records.push(record),
+    }
+}
+
+/// Records every action completed while it is active, and prints a summary when finished.
+///
+/// Only one session is active at a time, starting a session replaces any active session. A
+/// replaced session has no records, and finishing or dropping it leaves the active session
+/// untouched. Dropping an unfinished session prints the summary.
+pub struct Session {
+    generation: u64,
+    finished: bool,
+}
+
+impl Session {
+    /// Start recording completed actions.
+    pub fn start() -> Self {
+        let generation = NEXT_GENERATION.fetch_add(1, Ordering::Relaxed);
+        *lock() = Some(Recording {
+            generation,
+            start: Instant::now(),
+            records: vec![],
+        });
+
+        Self {
+            generation,
+            finished: false,
+        }
+    }
+
+    /// Returns if this session is the active session.
+    pub fn is_active(&self) -> bool {
+        self.with_recording(|_| ()).is_some()
+    }
+
+    /// The records of the actions completed so far.
+    pub fn records(&self) -> Vec<ActionRecord> {
+        self.with_recording(|recording| recording.records.clone())
+            .unwrap_or_default()
+    }
+
+    /// The counts of the actions completed so far.
+    pub fn summary(&self) -> SessionSummary {
+        self.with_recording(Recording::summary).unwrap_or_default()
+    }
+
+    /// Stop recording, print the failed actions and the summary, and return the summary.
+    ///
+    /// If this session was replaced, nothing is printed and the summary is empty.
+    pub fn finish(mut self) -> SessionSummary {
+        self.finished = true;
+        self.take_recording()
+            .map(|recording| recording.print_summary())
+            .unwrap_or_default()
+    }
+
+    /// Call `f` with the recording if this session still owns it.
+    fn with_recording<R, F: FnOnce(&Recording) -> R>(&self, f: F) -> Option<R> {
+        lock()
+            .as_ref()
+            .filter(|recording| recording.generation == self.generation)
+            .map(f)
+    }
+
+    /// Stop recording if this session still owns the recording.
+    fn take_recording(&self) -> Option<Recording> {
+        let mut recording = lock();
+        match recording.as_ref() {
+            Some(owned) if owned.generation == self.generation => recording.take(),
+            _ => None,
+        }
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        if !self.finished
+            && let Some(recording) = self.take_recording()
+        {
+            recording.print_summary();
+        }
+    }
+}
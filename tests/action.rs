@@ -0,0 +1,443 @@
+//! Tests for action output written through a sink.
+
+mod common;
+
+use core::{
+    fmt,
+    future::{self, Future},
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+use std::{io, thread, time::Duration};
+
+use serde_json::Value;
+use ts_cli_helper::{
+    Action, ActionErrorResult, ActionFailureResult, ActionMessageResult, ActionResult,
+    ActionSkipResult, ErrorDetail, MultiAction, OutputChoice, OutputFormat, RunOptions, State,
+    print_info, set_cursor_control, set_format,
+};
+use ts_rust_helper::style::ERASE_LINE_UP;
+
+use common::capture;
+
+#[derive(Debug)]
+struct WriteError(io::Error);
+
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("could not write")
+    }
+}
+
+impl core::error::Error for WriteError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[test]
+fn children_are_indented() {
+    let capture = capture();
+
+    let mut parent = Action::new("Building", "Built", "crate", 1);
+    let mut child = parent.child("Compiling", "Compiled", "module");
+    let mut grandchild = child.child("Parsing", "Parsed", "file");
+    grandchild.set_state(State::Success);
+    child.set_state(State::Success);
+    parent.set_state(State::Success);
+
+    assert_eq!(
+        capture.stdout(),
+        " Building crate\n   Compiling module\n     Parsing file\n     Parsed file\n   \
+         Compiled module\n Built crate\n"
+    );
+}
+
+#[test]
+fn failed_child_fails_parent() {
+    let capture = capture();
+
+    let mut parent = Action::new("Building", "Built", "crate", 0);
+    let mut child = parent.child("Compiling", "Compiled", "module");
+    child.set_state(State::Error);
+    assert!(parent.has_failed_child());
+    parent.set_state(State::Success);
+
+    assert_eq!(parent.state, State::Error);
+    assert!(capture.stdout().ends_with("Building crate failed\n"));
+}
+
+#[test]
+fn warned_child_warns_parent() {
+    let capture = capture();
+
+    let mut parent = Action::new("Building", "Built", "crate", 0);
+    let mut child = parent.child("Compiling", "Compiled", "module");
+    child.mark_warning();
+    child.set_state(State::Success);
+    parent.set_state(State::Success);
+
+    assert_eq!(child.state, State::Warning);
+    assert_eq!(parent.state, State::Warning);
+    assert!(capture.stdout().ends_with("Built crate with warnings\n"));
+}
+
+#[test]
+fn skipped_child_does_not_affect_parent() {
+    let _capture = capture();
+
+    let mut parent = Action::new("Building", "Built", "crate", 0);
+    let mut child = parent.child("Compiling", "Compiled", "module");
+    child.skip();
+    parent.set_state(State::Success);
+
+    assert_eq!(parent.state, State::Success);
+}
+
+#[test]
+fn json_children_reference_parent() {
+    let capture = capture();
+    set_format(OutputFormat::Json);
+
+    let parent = Action::new("Building", "Built", "crate", 0);
+    let mut child = parent.child("Compiling", "Compiled", "module");
+    child.set_state(State::Success);
+
+    let lines: Vec<Value> = capture
+        .stdout()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines[1]["parent"], lines[0]["id"]);
+    assert_eq!(lines[1]["indent"], 2);
+}
+
+#[test]
+fn progress_with_large_total() {
+    let capture = capture();
+    set_cursor_control(OutputChoice::Always);
+
+    let mut action = Action::progress("Copying", "Copied", "bytes", 0, u64::MAX);
+    action.set_position(u64::MAX / 2);
+    action.inc(u64::MAX);
+
+    assert_eq!(action.position(), Some((u64::MAX, u64::MAX)));
+    assert!(capture.stdout().contains(" 49% "));
+    assert!(
+        capture
+            .stdout()
+            .contains("100% 18446744073709551615/18446744073709551615")
+    );
+}
+
+#[test]
+fn multi_action_redraws_pinned_lines() {
+    let capture = capture();
+    set_cursor_control(OutputChoice::Always);
+
+    let multi = MultiAction::new();
+    let mut a = multi.action("Building", "Built", "a", 0);
+    let mut b = multi.action("Building", "Built", "b", 0);
+    a.set_state(State::Success);
+    print_info("note");
+    b.set_state(State::Success);
+
+    let erase = ERASE_LINE_UP;
+    assert_eq!(
+        capture.stdout(),
+        format!(
+            "Building a\n\
+             {erase}Building a\nBuilding b\n\
+             {erase}{erase}Built a\nBuilding b\n\
+             {erase}info: note\nBuilding b\n\
+             {erase}Built b\n"
+        )
+    );
+
+    // The region is empty, so later output is written normally.
+    print_info("after");
+    assert!(
+        capture
+            .stdout()
+            .ends_with(&format!("{erase}Built b\ninfo: after\n"))
+    );
+}
+
+#[test]
+fn multi_action_without_cursor_control() {
+    let capture = capture();
+
+    let multi = MultiAction::new();
+    let mut a = multi.action("Building", "Built", "a", 0);
+    let mut b = multi.action("Building", "Built", "b", 0);
+    b.set_state(State::Success);
+    a.set_state(State::Error);
+
+    assert_eq!(
+        capture.stdout(),
+        "Building a\nBuilding b\nBuilt b\nBuilding a failed\n"
+    );
+}
+
+#[test]
+fn display_errors_are_recorded() {
+    let capture = capture();
+
+    let action =
+        Action::new("Building", "Built", "crate", 0).with_error_detail(ErrorDetail::Message);
+    let result = Err::<(), String>("missing file".to_string()).bind_result_message(action);
+    assert!(result.is_err());
+
+    let mut action =
+        Action::new("Building", "Built", "crate", 0).with_error_detail(ErrorDetail::Message);
+    let _ = Err::<(), &str>("bad input").bind_error_message(&mut action);
+    assert_eq!(action.state, State::Error);
+
+    assert!(
+        capture
+            .stdout()
+            .contains("Building crate failed: missing file\n")
+    );
+    assert!(
+        capture
+            .stdout()
+            .ends_with("Building crate failed: bad input\n")
+    );
+}
+
+#[test]
+fn errors_without_display_are_bound() {
+    let capture = capture();
+
+    let action = Action::new("Building", "Built", "crate", 0);
+    let _ = Err::<(), ()>(()).bind_result(action);
+
+    let action = Action::new("Building", "Built", "crate", 0);
+    let _ = Err::<Option<()>, ()>(()).bind_skippable(action);
+
+    let _ = Action::run("Building", "Built", "crate", 0, |_| Err::<(), ()>(()));
+
+    assert_eq!(
+        capture.stdout().matches("Building crate failed\n").count(),
+        3
+    );
+}
+
+#[test]
+fn json_error_messages_are_recorded() {
+    let capture = capture();
+    set_format(OutputFormat::Json);
+
+    let action = Action::new("Building", "Built", "crate", 0);
+    let _ = Err::<(), String>("missing file".to_string()).bind_result_message(action);
+
+    let line: Value = serde_json::from_str(capture.stdout().lines().last().unwrap()).unwrap();
+    assert_eq!(line["state"], "error");
+    assert_eq!(line["error"], "missing file");
+}
+
+#[test]
+fn run_records_error_message() {
+    let capture = capture();
+
+    let result = Action::run("Building", "Built", "crate", 0, |action| {
+        Err::<(), String>("missing file".to_string()).bind_error_message(action)
+    });
+
+    assert!(result.is_err());
+    assert_eq!(
+        capture.stdout(),
+        "Building crate\nBuilding crate failed: missing file\n"
+    );
+}
+
+#[test]
+fn run_keeps_detailed_binding() {
+    let capture = capture();
+
+    let options = RunOptions::default().with_error_detail(ErrorDetail::Chain);
+    let _ = Action::run_with("Building", "Built", "crate", 0, options, |action| {
+        Err::<(), _>(WriteError(io::Error::other("disk full"))).bind_error_detailed(action)
+    });
+
+    assert!(
+        capture
+            .stdout()
+            .ends_with("Building crate failed: could not write\n  caused by: disk full\n")
+    );
+}
+
+#[test]
+fn run_child_inherits_options() {
+    let capture = capture();
+
+    let options = RunOptions::default()
+        .without_elapsed()
+        .with_error_detail(ErrorDetail::Hidden);
+    let _ = Action::run_with("Building", "Built", "crate", 0, options, |action| {
+        action.run_child("Compiling", "Compiled", "module", |_| {
+            Err::<(), _>("syntax error")
+        })
+    });
+
+    assert!(
+        capture
+            .stdout()
+            .ends_with("  Compiling module failed\nBuilding crate failed\n")
+    );
+}
+
+#[test]
+fn run_async_completes_action() {
+    let capture = capture();
+
+    let future = pin!(Action::run_async(
+        "Building",
+        "Built",
+        "crate",
+        0,
+        future::ready(Some(()))
+    ));
+    let poll = future.poll(&mut Context::from_waker(Waker::noop()));
+
+    assert_eq!(poll, Poll::Ready(Some(())));
+    assert_eq!(capture.stdout(), "Building crate\nBuilt crate\n");
+}
+
+#[test]
+fn dropped_action_future_is_cancelled() {
+    let capture = capture();
+
+    let future = Action::run_async(
+        "Building",
+        "Built",
+        "crate",
+        0,
+        future::pending::<Result<(), String>>(),
+    );
+    drop(future);
+
+    assert_eq!(
+        capture.stdout(),
+        "Building crate\nBuilding crate cancelled\n"
+    );
+}
+
+#[test]
+fn elapsed_shown_above_threshold() {
+    let capture = capture();
+
+    let mut action = Action::new("Building", "Built", "crate", 0).with_elapsed(Duration::ZERO);
+    action.set_state(State::Success);
+
+    let stdout = capture.stdout();
+    let line = stdout.lines().last().unwrap();
+    assert!(line.starts_with("Built crate ("), "{line}");
+    assert!(line.ends_with("ms)"), "{line}");
+}
+
+#[test]
+fn elapsed_hidden_below_threshold() {
+    let capture = capture();
+
+    let mut action =
+        Action::new("Building", "Built", "crate", 0).with_elapsed(Duration::from_secs(3600));
+    action.set_state(State::Success);
+
+    assert_eq!(capture.stdout(), "Building crate\nBuilt crate\n");
+}
+
+#[test]
+fn spinner_replaced_by_final_line() {
+    let capture = capture();
+    set_cursor_control(OutputChoice::Always);
+
+    let mut action = Action::new("Building", "Built", "crate", 0).with_spinner();
+    thread::sleep(Duration::from_millis(250));
+    action.set_state(State::Success);
+
+    let stdout = capture.stdout();
+    assert!(stdout.starts_with(&format!("Building crate\n{ERASE_LINE_UP}Building crate ⠋ ")));
+    assert!(stdout.ends_with(&format!("{ERASE_LINE_UP}Built crate\n")));
+
+    // The spinner stopped, so nothing is redrawn after the final line.
+    thread::sleep(Duration::from_millis(250));
+    assert_eq!(capture.stdout(), stdout);
+}
+
+#[test]
+fn progress_without_cursor_control() {
+    let capture = capture();
+
+    let mut action = Action::progress("Copying", "Copied", "files", 0, 10);
+    action.inc(5);
+    action.set_state(State::Success);
+
+    assert_eq!(action.position(), Some((5, 10)));
+    let stdout = capture.stdout();
+    assert_eq!(stdout.lines().count(), 2, "{stdout}");
+    assert!(stdout.ends_with("\nCopied files\n"));
+}
+
+#[test]
+fn dropped_action_fails() {
+    let capture = capture();
+
+    drop(Action::new("Building", "Built", "crate", 0));
+
+    assert_eq!(capture.stdout(), "Building crate\nBuilding crate failed\n");
+}
+
+#[test]
+fn dropped_action_with_cancelled_drop_state() {
+    let capture = capture();
+
+    drop(Action::new("Building", "Built", "crate", 0).with_drop_state(State::Cancelled));
+
+    assert_eq!(
+        capture.stdout(),
+        "Building crate\nBuilding crate cancelled\n"
+    );
+}
+
+#[test]
+fn dropped_action_with_in_progress_drop_state() {
+    let capture = capture();
+
+    drop(Action::new("Building", "Built", "crate", 0).with_drop_state(State::InProgress));
+
+    assert_eq!(capture.stdout(), "Building crate\n");
+}
+
+#[test]
+fn dropped_child_fails_parent() {
+    let capture = capture();
+
+    let mut parent = Action::new("Building", "Built", "crate", 0);
+    drop(parent.child("Compiling", "Compiled", "module"));
+    parent.set_state(State::Success);
+
+    assert_eq!(parent.state, State::Error);
+    assert!(
+        capture
+            .stdout()
+            .ends_with("  Compiling module failed\nBuilding crate failed\n")
+    );
+}
+
+#[test]
+fn bind_result_or_uses_failure_state() {
+    let capture = capture();
+
+    let action = Action::new("Building", "Built", "crate", 0);
+    let _ = Err::<(), ()>(()).bind_result_or(action, State::Warning);
+
+    let action = Action::new("Building", "Built", "crate", 0);
+    let _ = None::<()>.bind_result_or(action, State::Cancelled);
+
+    assert!(
+        capture
+            .stdout()
+            .ends_with("Built crate with warnings\nBuilding crate\nBuilding crate cancelled\n")
+    );
+}
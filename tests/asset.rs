@@ -0,0 +1,65 @@
+//! Tests for embedding assets.
+
+#![cfg(feature = "generate")]
+
+mod common;
+
+use std::fs;
+
+use quote::ToTokens;
+use ts_cli_helper::{Assets, ConflictPolicy, ParseFileErrorKind, ParseOptions};
+
+use common::TempDir;
+
+#[test]
+fn conflicting_asset_names() {
+    let dir = TempDir::new("asset-conflict");
+    fs::write(dir.join("a-b.txt"), "a").unwrap();
+    fs::write(dir.join("a_b.txt"), "b").unwrap();
+
+    let error = Assets::parse(dir.path()).err().unwrap();
+    let ParseFileErrorKind::ModuleConflict { source, .. } = error.kind else {
+        panic!("expected a module conflict, got {error}");
+    };
+    assert_eq!(source.name, "a_b_txt");
+
+    let options = ParseOptions::default().with_conflict_policy(ConflictPolicy::Override);
+    let assets = Assets::parse_with(dir.path(), &options).unwrap();
+    assert_eq!(assets.assets.len(), 1);
+    assert_eq!(assets.assets[0].file_name, "a_b.txt");
+}
+
+#[test]
+fn text_is_derived_from_bytes() {
+    let dir = TempDir::new("asset-text");
+    fs::write(dir.join("readme.txt"), "hello").unwrap();
+
+    let tokens = Assets::parse(dir.path())
+        .unwrap()
+        .to_token_stream()
+        .to_string();
+    assert_eq!(tokens.matches("include_bytes !").count(), 1);
+    assert!(!tokens.contains("include_str !"));
+    assert!(tokens.contains("pub const BYTES"));
+    assert!(tokens.contains("from_utf8 (BYTES)"));
+}
+
+#[test]
+fn include_paths_are_relative_to_target() {
+    let dir = TempDir::new("asset-write");
+    fs::create_dir(dir.join("images")).unwrap();
+    fs::create_dir(dir.join("src")).unwrap();
+    fs::write(dir.join("images").join("logo.png"), "a").unwrap();
+
+    let target = dir.join("src").join("assets.rs");
+    Assets::parse(&dir.join("images"))
+        .unwrap()
+        .write(Some(&target), "test", "0.0.0")
+        .unwrap();
+
+    let output = fs::read_to_string(target).unwrap();
+    assert!(
+        output.contains("include_bytes!(\"../images/logo.png\")"),
+        "{output}"
+    );
+}
@@ -0,0 +1,128 @@
+//! Helpers shared by the integration tests.
+
+#![allow(dead_code)]
+
+use std::{
+    convert::Infallible,
+    fs,
+    path::{Path, PathBuf},
+    process,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+#[cfg(feature = "generate")]
+use proc_macro2::TokenStream;
+#[cfg(feature = "generate")]
+use quote::ToTokens;
+use ts_cli_helper::{
+    FileParser, FileSource, MemorySink, OutputChoice, OutputFormat, ParseFrom, Verbosity,
+    set_color, set_cursor_control, set_format, set_sink, set_verbosity,
+};
+
+/// Serializes tests that use the global output state.
+static OUTPUT_LOCK: Mutex<()> = Mutex::new(());
+
+/// Output captured in memory, the global output state is reserved until this is dropped.
+pub struct Capture {
+    /// The sink the output is written to.
+    pub sink: MemorySink,
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl Capture {
+    /// The output written to `stdout`.
+    pub fn stdout(&self) -> String {
+        self.sink.stdout()
+    }
+
+    /// The output written to `stderr`.
+    pub fn stderr(&self) -> String {
+        self.sink.stderr()
+    }
+}
+
+/// Capture the output in memory with the default verbosity and format, without color or cursor
+/// control.
+pub fn capture() -> Capture {
+    let guard = OUTPUT_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+
+    set_verbosity(Verbosity::Normal);
+    set_format(OutputFormat::Human);
+    set_color(OutputChoice::Never);
+    set_cursor_control(OutputChoice::Never);
+
+    let sink = MemorySink::new();
+    set_sink(sink.clone());
+
+    Capture {
+        sink,
+        _guard: guard,
+    }
+}
+
+/// An empty temporary directory, removed when this is dropped.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Create an empty directory for a test, `name` must be unique to the test.
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("ts-cli-helper-{}-{name}", process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        Self { path }
+    }
+
+    /// The path to the directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The path to an entry in the directory.
+    pub fn join<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        self.path.join(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Module contents holding the text of the file.
+pub struct Text(pub String);
+
+impl ParseFrom<String, ()> for Text {
+    type Error = Infallible;
+
+    fn parse(source: String, _: &mut ()) -> Result<Self, Self::Error> {
+        Ok(Self(source))
+    }
+}
+
+/// Module contents that ignore the file and generate nothing.
+pub struct Empty;
+
+impl ParseFrom<String, ()> for Empty {
+    type Error = Infallible;
+
+    fn parse(_: String, _: &mut ()) -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+#[cfg(feature = "generate")]
+impl ToTokens for Empty {
+    fn to_tokens(&self, _: &mut TokenStream) {}
+}
+
+/// The names of the parsed modules.
+pub fn names<T: ParseFrom<S, ()>, S: FileSource>(parser: &FileParser<(), T, S>) -> Vec<&str> {
+    parser
+        .modules
+        .iter()
+        .map(|module| module.name.as_str())
+        .collect()
+}
@@ -0,0 +1,109 @@
+//! Tests for module docs from the parsed contents, sidecar files, and leading comments.
+
+mod common;
+
+use std::{convert::Infallible, fs};
+
+use ts_cli_helper::{FileParser, ParseFrom, ParseOptions};
+
+use common::{TempDir, Text, names};
+
+/// Module contents with docs if the source contains `DOCS`.
+struct Documented(bool);
+
+impl ParseFrom<String, ()> for Documented {
+    type Error = Infallible;
+
+    fn parse(source: String, _: &mut ()) -> Result<Self, Self::Error> {
+        Ok(Self(source.contains("DOCS")))
+    }
+
+    fn docs(&self) -> Option<String> {
+        self.0.then(|| "parsed".to_string())
+    }
+}
+
+fn options() -> ParseOptions {
+    ParseOptions::default()
+        .with_leading_comment("//")
+        .with_sidecar_docs()
+}
+
+#[test]
+fn docs_precedence() {
+    let dir = TempDir::new("docs-precedence");
+    fs::write(dir.join("a.txt"), "// comment\nDOCS").unwrap();
+    fs::write(dir.join("a.txt.md"), "sidecar").unwrap();
+    fs::write(dir.join("b.txt"), "// comment\n").unwrap();
+    fs::write(dir.join("b.txt.md"), "sidecar").unwrap();
+    fs::write(dir.join("c.txt"), "// comment\n").unwrap();
+
+    let parser = FileParser::<(), Documented>::parse_with(dir.path(), &mut (), &options()).unwrap();
+    let docs = |name: &str| {
+        parser
+            .modules
+            .iter()
+            .find(|module| module.name == name)
+            .and_then(|module| module.docs.as_deref())
+    };
+
+    assert_eq!(docs("a"), Some("parsed"));
+    assert_eq!(docs("b"), Some("sidecar"));
+    assert_eq!(docs("c"), Some("comment"));
+}
+
+#[test]
+fn sidecar_files_are_not_modules() {
+    let dir = TempDir::new("docs-sidecar-files");
+    fs::write(dir.join("shader.glsl"), "").unwrap();
+    fs::write(dir.join("shader.glsl.md"), "docs").unwrap();
+    fs::write(dir.join("notes.md"), "").unwrap();
+
+    let parser = FileParser::<(), Text>::parse_with(dir.path(), &mut (), &options()).unwrap();
+    assert_eq!(names(&parser), ["notes", "shader"]);
+
+    let parser = FileParser::<(), Text>::parse(dir.path(), &mut ()).unwrap();
+    assert_eq!(names(&parser), ["notes", "shader", "shader_glsl"]);
+}
+
+#[test]
+fn leading_comment_after_bom_and_blank_lines() {
+    let dir = TempDir::new("docs-leading-comment");
+    fs::write(
+        dir.join("a.txt"),
+        "\u{feff}\n  \n  // first line\n//second line  \ncode\n// not docs",
+    )
+    .unwrap();
+
+    let parser = FileParser::<(), Text>::parse_with(dir.path(), &mut (), &options()).unwrap();
+    assert_eq!(
+        parser.modules[0].docs.as_deref(),
+        Some("first line\nsecond line")
+    );
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn docs_are_emitted_before_provenance() {
+    use quote::{ToTokens, quote};
+
+    use common::Empty;
+
+    let dir = TempDir::new("docs-emitted");
+    fs::write(dir.join("a.txt"), "").unwrap();
+    fs::write(dir.join("a.txt.md"), "First line.\n\nSecond line.\n").unwrap();
+
+    let tokens = FileParser::<(), Empty>::parse_with(&dir.join("a.txt"), &mut (), &options())
+        .unwrap()
+        .to_token_stream()
+        .to_string();
+    let expected = quote! {
+        #[doc = " First line."]
+        #[doc = ""]
+        #[doc = " Second line."]
+        #[doc = ""]
+        #[doc = " Generated from `a.txt` with FNV-1a hash `0xcbf29ce484222325`."]
+        pub mod a {}
+    };
+    assert_eq!(tokens, expected.to_string());
+}
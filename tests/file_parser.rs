@@ -0,0 +1,135 @@
+//! Tests for parsing files and directories with pathological paths.
+
+mod common;
+
+use std::{convert::Infallible, fs, path::Path};
+
+use ts_cli_helper::{FileParser, ParseFileError, ParseFileErrorKind};
+
+use common::{TempDir, Text, names};
+
+fn parse(source: &Path) -> Result<FileParser<(), Text>, ParseFileError<Infallible>> {
+    FileParser::parse(source, &mut ())
+}
+
+#[test]
+fn parent_dir_root() {
+    let dir = TempDir::new("parent-dir-root");
+    fs::create_dir(dir.join("child")).unwrap();
+    fs::write(dir.join("a.txt"), "a").unwrap();
+
+    let parser = parse(&dir.join("child").join("..")).unwrap();
+    assert_eq!(names(&parser), ["a"]);
+}
+
+#[test]
+fn current_dir_root() {
+    let dir = TempDir::new("current-dir-root");
+    fs::write(dir.join("a.txt"), "a").unwrap();
+
+    let parser = parse(&dir.join(".")).unwrap();
+    assert_eq!(names(&parser), ["a"]);
+}
+
+#[test]
+fn empty_directory() {
+    let dir = TempDir::new("empty-directory");
+
+    let parser = parse(dir.path()).unwrap();
+    assert!(parser.modules.is_empty());
+}
+
+#[test]
+fn missing_root() {
+    let dir = TempDir::new("missing-root");
+
+    let error = parse(&dir.join("missing")).err().unwrap();
+    assert!(matches!(
+        error.kind,
+        ParseFileErrorKind::ReadSourceMetadata { .. }
+    ));
+}
+
+#[test]
+fn file_name_without_ident_characters() {
+    let dir = TempDir::new("no-ident-characters");
+    fs::write(dir.join("-.txt"), "a").unwrap();
+
+    let error = parse(dir.path()).err().unwrap();
+    assert!(matches!(
+        error.kind,
+        ParseFileErrorKind::InvalidFileName { .. }
+    ));
+}
+
+#[test]
+fn keyword_file_name() {
+    let dir = TempDir::new("keyword-file-name");
+    fs::write(dir.join("type.txt"), "a").unwrap();
+    fs::write(dir.join("self.txt"), "b").unwrap();
+
+    let parser = parse(dir.path()).unwrap();
+    assert_eq!(names(&parser), ["self_", "type_"]);
+}
+
+#[test]
+fn dot_file_name() {
+    let dir = TempDir::new("dot-file-name");
+    fs::write(dir.join(".config"), "a").unwrap();
+
+    let parser = parse(dir.path()).unwrap();
+    assert_eq!(names(&parser), ["config"]);
+}
+
+#[test]
+fn subdirectories_are_ignored() {
+    let dir = TempDir::new("subdirectories");
+    fs::create_dir(dir.join("nested.txt")).unwrap();
+    fs::write(dir.join("a.txt"), "a").unwrap();
+
+    let parser = parse(dir.path()).unwrap();
+    assert_eq!(names(&parser), ["a"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_file_name() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    let dir = TempDir::new("non-utf8-file-name");
+    fs::write(dir.join(OsStr::from_bytes(b"\xffname.txt")), "a").unwrap();
+
+    let parser = parse(dir.path()).unwrap();
+    assert_eq!(names(&parser), ["name"]);
+    assert_eq!(parser.modules[0].contents.0, "a");
+}
+
+#[cfg(all(feature = "json", feature = "generate-data"))]
+#[test]
+fn keyword_names_in_generated_code() {
+    use quote::{ToTokens, quote};
+    use ts_cli_helper::{Index, Json, Value};
+
+    let dir = TempDir::new("keyword-generated-code");
+    fs::write(dir.join("self.json"), r#"{"self": 1, "type": "a"}"#).unwrap();
+
+    let parser = FileParser::<(), Json<Value>>::parse(dir.path(), &mut ()).unwrap();
+    assert_eq!(parser.modules[0].name, "self_");
+
+    let tokens = parser
+        .with_index(Index::default())
+        .to_token_stream()
+        .to_string();
+
+    let expected = [
+        quote! { pub mod self_ },
+        quote! { pub struct Data { pub self_: i64, pub type_: &'static str, } },
+        quote! { Data { self_: 1i64, type_: "a" } },
+        quote! { pub enum ModuleName { Self_, } },
+        quote! { Self::Self_ => "self_" },
+    ];
+    for expected in expected {
+        let expected = expected.to_string();
+        assert!(tokens.contains(&expected), "missing `{expected}`");
+    }
+}
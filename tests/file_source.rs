@@ -0,0 +1,42 @@
+//! Tests for reading files into the different file sources.
+
+mod common;
+
+use std::{convert::Infallible, fs};
+
+use ts_cli_helper::{FileParser, FileSource, ParseFileErrorKind, ParseFrom};
+
+use common::{TempDir, Text};
+
+struct Bytes(Vec<u8>);
+
+impl<S: FileSource> ParseFrom<S, ()> for Bytes {
+    type Error = Infallible;
+
+    fn parse(source: S, _: &mut ()) -> Result<Self, Self::Error> {
+        Ok(Self(source.as_bytes().to_vec()))
+    }
+}
+
+#[test]
+fn binary_contents() {
+    let dir = TempDir::new("binary-contents");
+    fs::write(dir.join("image.bin"), [0xff, 0x00, 0xfe]).unwrap();
+
+    let error = FileParser::<(), Text>::parse(dir.path(), &mut ())
+        .err()
+        .unwrap();
+    assert!(matches!(error.kind, ParseFileErrorKind::ReadFile { .. }));
+
+    let parser = FileParser::<(), Bytes, Vec<u8>>::parse(dir.path(), &mut ()).unwrap();
+    assert_eq!(parser.modules[0].contents.0, [0xff, 0x00, 0xfe]);
+}
+
+#[test]
+fn text_as_bytes() {
+    let dir = TempDir::new("text-as-bytes");
+    fs::write(dir.join("a.txt"), "a").unwrap();
+
+    let parser = FileParser::<(), Bytes, Vec<u8>>::parse(dir.path(), &mut ()).unwrap();
+    assert_eq!(parser.modules[0].contents.0, b"a");
+}
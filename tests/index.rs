@@ -0,0 +1,65 @@
+//! Tests for the index generated alongside the modules.
+
+#![cfg(feature = "generate")]
+
+mod common;
+
+use std::fs;
+
+use quote::{ToTokens, quote};
+use ts_cli_helper::{FileParser, Index};
+
+use common::{Empty, TempDir};
+
+fn index_tokens(dir: &TempDir) -> String {
+    FileParser::<(), Empty>::parse(dir.path(), &mut ())
+        .unwrap()
+        .with_index(Index::default())
+        .to_token_stream()
+        .to_string()
+}
+
+#[test]
+fn sources_are_relative_to_root() {
+    let dir = TempDir::new("index-relative-sources");
+    fs::create_dir(dir.join("shaders")).unwrap();
+    fs::write(dir.join("shaders").join("blur.wgsl"), "").unwrap();
+
+    let parser = FileParser::<(), Empty>::parse(&dir.join("shaders"), &mut ()).unwrap();
+    assert_eq!(
+        parser.modules[0].relative_source,
+        ["shaders", "blur.wgsl"]
+            .iter()
+            .collect::<std::path::PathBuf>()
+    );
+
+    let tokens = parser
+        .with_index(Index::default())
+        .to_token_stream()
+        .to_string();
+    let sources = quote! { &[("blur", "shaders/blur.wgsl")] }.to_string();
+    assert!(tokens.contains(&sources));
+    assert!(!tokens.contains(&dir.path().to_string_lossy().to_string()));
+}
+
+#[test]
+fn reserved_variants_are_suffixed() {
+    let dir = TempDir::new("index-reserved-variants");
+    fs::write(dir.join("self.txt"), "").unwrap();
+    fs::write(dir.join("type.txt"), "").unwrap();
+
+    let tokens = index_tokens(&dir);
+    let variants = quote! { pub enum ModuleName { Self_, Type, } }.to_string();
+    assert!(tokens.contains(&variants));
+}
+
+#[test]
+fn duplicate_variants_are_reported() {
+    let dir = TempDir::new("index-duplicate-variants");
+    fs::write(dir.join("v1_0.txt"), "").unwrap();
+    fs::write(dir.join("v10.txt"), "").unwrap();
+
+    let tokens = index_tokens(&dir);
+    assert!(tokens.contains("compile_error !"));
+    assert!(tokens.contains("both produce the variant `V10`"));
+}
@@ -0,0 +1,109 @@
+//! Tests for parsing several roots and merging parsers.
+
+mod common;
+
+use std::{convert::Infallible, fs};
+
+use ts_cli_helper::{ConflictPolicy, FileParser, ParseFileError, ParseFileErrorKind, ParseOptions};
+
+use common::{TempDir, Text};
+
+/// Create two roots, `first` with `a.txt` and `b.txt`, and `second` with `b.txt` and `c.txt`.
+fn roots(name: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    for (root, file) in [
+        ("first", "a"),
+        ("first", "b"),
+        ("second", "b"),
+        ("second", "c"),
+    ] {
+        fs::create_dir_all(dir.join(root)).unwrap();
+        fs::write(dir.join(root).join(format!("{file}.txt")), root).unwrap();
+    }
+    dir
+}
+
+fn parse_roots(
+    dir: &TempDir,
+    policy: ConflictPolicy,
+) -> Result<FileParser<(), Text>, ParseFileError<Infallible>> {
+    let options = ParseOptions::default().with_conflict_policy(policy);
+    FileParser::parse_roots(&[dir.join("first"), dir.join("second")], &mut (), &options)
+}
+
+fn contents(parser: &FileParser<(), Text>) -> Vec<(&str, &str)> {
+    parser
+        .modules
+        .iter()
+        .map(|module| (module.name.as_str(), module.contents.0.as_str()))
+        .collect()
+}
+
+#[test]
+fn later_root_overrides() {
+    let dir = roots("roots-override");
+
+    let parser = parse_roots(&dir, ConflictPolicy::Override).unwrap();
+    assert_eq!(
+        contents(&parser),
+        [("a", "first"), ("b", "second"), ("c", "second")]
+    );
+}
+
+#[test]
+fn conflicting_roots_error() {
+    let dir = roots("roots-error");
+
+    let error = parse_roots(&dir, ConflictPolicy::Error).err().unwrap();
+    let ParseFileErrorKind::ModuleConflict { source, .. } = error.kind else {
+        panic!("expected a module conflict");
+    };
+    assert_eq!(source.name, "b");
+    assert_eq!(source.existing, dir.join("first").join("b.txt"));
+    assert_eq!(source.conflicting, dir.join("second").join("b.txt"));
+}
+
+#[test]
+fn same_root_conflicts_use_policy() {
+    let dir = TempDir::new("same-root-conflict");
+    fs::write(dir.join("a.json"), "json").unwrap();
+    fs::write(dir.join("a.txt"), "text").unwrap();
+
+    let error = FileParser::<(), Text>::parse(dir.path(), &mut ())
+        .err()
+        .unwrap();
+    assert!(matches!(
+        error.kind,
+        ParseFileErrorKind::ModuleConflict { .. }
+    ));
+
+    let options = ParseOptions::default().with_conflict_policy(ConflictPolicy::Override);
+    let parser = FileParser::<(), Text>::parse_with(dir.path(), &mut (), &options).unwrap();
+    assert_eq!(contents(&parser), [("a", "text")]);
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn merge_keeps_first_index() {
+    use ts_cli_helper::Index;
+
+    let dir = roots("merge-index");
+    let parse = |root: &str| FileParser::<(), Text>::parse(&dir.join(root), &mut ()).unwrap();
+
+    let merged = parse("first")
+        .with_index(Index::new("First"))
+        .merge(
+            parse("second").with_index(Index::new("Second")),
+            ConflictPolicy::Override,
+        )
+        .unwrap();
+    assert_eq!(merged.index(), Some(&Index::new("First")));
+
+    let merged = parse("first")
+        .merge(
+            parse("second").with_index(Index::new("Second")),
+            ConflictPolicy::Override,
+        )
+        .unwrap();
+    assert_eq!(merged.index(), Some(&Index::new("Second")));
+}
@@ -0,0 +1,252 @@
+//! Tests for log and action output written through a sink.
+
+mod common;
+
+use ts_cli_helper::{
+    Action, MemorySink, OutputChoice, OutputFormat, State, Verbosity, print_debug, print_error,
+    print_fail, print_info, print_success, print_trace, print_warning, set_color,
+    set_cursor_control, set_format, set_verbosity,
+};
+use ts_rust_helper::style::{BOLD, ERASE_LINE_UP, RED, RESET};
+
+use common::capture;
+use serde_json::Value;
+
+/// Parse each line of the output as a JSON object.
+fn json_lines(output: &str) -> Vec<Value> {
+    output
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn errors_and_warnings_go_to_stderr() {
+    let capture = capture();
+
+    print_error("bad");
+    print_warning("careful");
+    print_fail("broken");
+
+    assert_eq!(
+        capture.stderr(),
+        "error: bad\nwarning: careful\nFail: broken\n"
+    );
+    assert_eq!(capture.stdout(), "");
+}
+
+#[test]
+fn success_and_info_go_to_stdout() {
+    let capture = capture();
+
+    print_success("done");
+    print_info("note");
+
+    assert_eq!(capture.stdout(), "Success: done\ninfo: note\n");
+    assert_eq!(capture.stderr(), "");
+}
+
+#[test]
+fn actions_go_to_stdout() {
+    let capture = capture();
+
+    let mut action = Action::new("Building", "Built", "crate", 0);
+    action.set_state(State::Success);
+
+    assert_eq!(capture.stdout(), "Building crate\nBuilt crate\n");
+    assert_eq!(capture.stderr(), "");
+}
+
+#[test]
+fn memory_sink_clones_share_output() {
+    let capture = capture();
+    let clone: MemorySink = capture.sink.clone();
+
+    print_info("shared");
+    assert_eq!(clone.stdout(), "info: shared\n");
+
+    clone.clear();
+    assert_eq!(capture.stdout(), "");
+}
+
+#[test]
+fn styles_kept_when_color_enabled() {
+    let capture = capture();
+    set_color(OutputChoice::Always);
+
+    print_error("bad");
+
+    assert_eq!(
+        capture.stderr(),
+        format!("{BOLD}{RED}error{RESET}{BOLD}:{RESET} bad\n")
+    );
+}
+
+#[test]
+fn styles_stripped_when_color_disabled() {
+    let capture = capture();
+
+    print_error("bad");
+
+    assert_eq!(capture.stderr(), "error: bad\n");
+}
+
+#[test]
+fn cursor_control_kept_when_color_disabled() {
+    let capture = capture();
+    set_cursor_control(OutputChoice::Always);
+
+    let mut action = Action::new("Building", "Built", "crate", 0);
+    action.set_state(State::Success);
+
+    assert_eq!(
+        capture.stdout(),
+        format!("Building crate\n{ERASE_LINE_UP}Built crate\n")
+    );
+}
+
+#[test]
+fn lines_not_overwritten_without_cursor_control() {
+    let capture = capture();
+    set_color(OutputChoice::Always);
+
+    let mut action = Action::new("Building", "Built", "crate", 0);
+    action.set_state(State::Success);
+
+    assert!(!capture.stdout().contains(ERASE_LINE_UP));
+}
+
+#[test]
+fn lines_only_overwritten_by_their_owner() {
+    let capture = capture();
+    set_cursor_control(OutputChoice::Always);
+
+    let mut action = Action::new("Building", "Built", "crate", 0);
+    print_info("interrupted");
+    action.set_state(State::Success);
+
+    assert_eq!(
+        capture.stdout(),
+        "Building crate\ninfo: interrupted\nBuilt crate\n"
+    );
+}
+
+#[test]
+fn quiet_only_prints_errors_and_failures() {
+    let capture = capture();
+    set_verbosity(Verbosity::Quiet);
+
+    print_error("bad");
+    print_fail("broken");
+    print_warning("careful");
+    print_success("done");
+    print_info("note");
+    print_debug("details");
+
+    let mut success = Action::new("Building", "Built", "a", 0);
+    success.set_state(State::Success);
+    let mut error = Action::new("Building", "Built", "b", 0);
+    error.set_state(State::Error);
+
+    assert_eq!(capture.stderr(), "error: bad\nFail: broken\n");
+    assert_eq!(capture.stdout(), "Building b failed\n");
+}
+
+#[test]
+fn normal_hides_debug_and_trace() {
+    let capture = capture();
+
+    print_info("note");
+    print_debug("details");
+    print_trace("everything");
+
+    assert_eq!(capture.stdout(), "info: note\n");
+}
+
+#[test]
+fn verbose_shows_debug() {
+    let capture = capture();
+    set_verbosity(Verbosity::Verbose);
+
+    print_debug("details");
+    print_trace("everything");
+
+    assert_eq!(capture.stdout(), "debug: details\n");
+}
+
+#[test]
+fn trace_shows_everything() {
+    let capture = capture();
+    set_verbosity(Verbosity::Trace);
+
+    print_debug("details");
+    print_trace("everything");
+
+    assert_eq!(capture.stdout(), "debug: details\ntrace: everything\n");
+}
+
+#[test]
+fn verbosity_from_flags() {
+    assert_eq!(Verbosity::from_flags(true, 2), Verbosity::Quiet);
+    assert_eq!(Verbosity::from_flags(false, 0), Verbosity::Normal);
+    assert_eq!(Verbosity::from_flags(false, 1), Verbosity::Verbose);
+    assert_eq!(Verbosity::from_flags(false, 5), Verbosity::Trace);
+}
+
+#[test]
+fn json_log_lines() {
+    let capture = capture();
+    set_format(OutputFormat::Json);
+    set_color(OutputChoice::Always);
+
+    print_error("bad \"quoted\"\nline");
+    print_info("note");
+
+    let errors = json_lines(&capture.stderr());
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0]["type"], "log");
+    assert_eq!(errors[0]["level"], "error");
+    assert_eq!(errors[0]["message"], "bad \"quoted\"\nline");
+    assert!(errors[0]["timestamp"].is_u64());
+
+    let infos = json_lines(&capture.stdout());
+    assert_eq!(infos[0]["level"], "info");
+    assert_eq!(infos[0]["message"], "note");
+}
+
+#[test]
+fn json_action_lines() {
+    let capture = capture();
+    set_format(OutputFormat::Json);
+    set_cursor_control(OutputChoice::Always);
+
+    let mut action = Action::new("Building", "Built", "crate", 2);
+    action.set_state(State::Success);
+
+    let lines = json_lines(&capture.stdout());
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["type"], "action");
+    assert_eq!(lines[0]["state"], "in_progress");
+    assert_eq!(lines[1]["state"], "success");
+    assert_eq!(lines[1]["id"], lines[0]["id"]);
+    assert_eq!(lines[1]["actioning_verb"], "Building");
+    assert_eq!(lines[1]["actioned_verb"], "Built");
+    assert_eq!(lines[1]["detail"], "crate");
+    assert_eq!(lines[1]["indent"], 2);
+    assert!(lines[1]["elapsed_ms"].is_u64());
+    assert!(lines[1].get("parent").is_none());
+}
+
+#[test]
+fn json_action_errors() {
+    let capture = capture();
+    set_format(OutputFormat::Json);
+
+    let mut action = Action::new("Building", "Built", "crate", 0);
+    action.set_error_message("missing file");
+
+    let lines = json_lines(&capture.stdout());
+    assert_eq!(lines[1]["state"], "error");
+    assert_eq!(lines[1]["error"], "missing file");
+    assert_eq!(lines[1]["sources"], Value::Array(vec![]));
+}
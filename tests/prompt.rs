@@ -0,0 +1,73 @@
+//! Tests for prompts that fail or answer before asking the user.
+
+use std::sync::{Mutex, MutexGuard};
+
+use ts_cli_helper::{PromptErrorKind, confirm, select, set_assume_yes, text};
+
+/// Serializes tests that assume yes, the setting is global.
+static ASSUME_YES: Mutex<()> = Mutex::new(());
+
+/// Assumes yes until dropped.
+struct AssumeYes {
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl AssumeYes {
+    fn new() -> Self {
+        let guard = ASSUME_YES.lock().unwrap_or_else(|error| error.into_inner());
+        set_assume_yes(true);
+        Self { _guard: guard }
+    }
+}
+
+impl Drop for AssumeYes {
+    fn drop(&mut self) {
+        set_assume_yes(false);
+    }
+}
+
+#[test]
+fn select_without_options() {
+    let error = select::<&str>("Pick one", &[], 0).err().unwrap();
+    assert!(matches!(error.kind, PromptErrorKind::NoOptions { .. }));
+}
+
+#[test]
+fn select_with_invalid_default() {
+    let error = select("Pick one", &["a", "b"], 2).err().unwrap();
+    assert!(matches!(
+        error.kind,
+        PromptErrorKind::InvalidDefault {
+            default: 2,
+            options: 2,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn confirm_assumes_yes() {
+    let _assume_yes = AssumeYes::new();
+    assert!(confirm("Continue?", false).unwrap());
+    assert!(confirm("Continue?", true).unwrap());
+}
+
+#[test]
+fn text_uses_default_without_validation() {
+    let _assume_yes = AssumeYes::new();
+    let answer = text("Name", Some("crate"), |_| Err("invalid".to_string())).unwrap();
+    assert_eq!(answer, "crate");
+}
+
+#[test]
+fn text_without_default() {
+    let _assume_yes = AssumeYes::new();
+    let error = text("Name", None, |_| Ok(())).err().unwrap();
+    assert!(matches!(error.kind, PromptErrorKind::NoDefault { .. }));
+}
+
+#[test]
+fn select_uses_default() {
+    let _assume_yes = AssumeYes::new();
+    assert_eq!(select("Pick one", &["a", "b"], 1).unwrap(), 1);
+}
@@ -0,0 +1,50 @@
+//! Tests for the provenance docs of generated modules and assets.
+
+#![cfg(feature = "generate")]
+
+mod common;
+
+use std::fs;
+
+use quote::ToTokens;
+use ts_cli_helper::{Assets, FileParser};
+
+use common::{Empty, TempDir};
+
+#[test]
+fn module_provenance_is_relative_to_root() {
+    let dir = TempDir::new("module-provenance");
+    fs::create_dir(dir.join("shaders")).unwrap();
+    fs::write(dir.join("shaders").join("blur.wgsl"), "a").unwrap();
+
+    let tokens = FileParser::<(), Empty>::parse(&dir.join("shaders"), &mut ())
+        .unwrap()
+        .to_token_stream()
+        .to_string();
+    assert!(tokens.contains(
+        "\" Generated from `shaders/blur.wgsl` with FNV-1a hash `0xaf63dc4c8601ec8c`.\""
+    ));
+
+    let tokens = FileParser::<(), Empty>::parse(&dir.join("shaders").join("blur.wgsl"), &mut ())
+        .unwrap()
+        .to_token_stream()
+        .to_string();
+    assert!(tokens.contains("\" Generated from `blur.wgsl` with"));
+}
+
+#[test]
+fn asset_provenance_is_relative_to_root() {
+    let dir = TempDir::new("asset-provenance");
+    fs::create_dir(dir.join("images")).unwrap();
+    fs::write(dir.join("images").join("logo.png"), "a").unwrap();
+
+    let tokens = Assets::parse(&dir.join("images"))
+        .unwrap()
+        .to_token_stream()
+        .to_string();
+    assert!(
+        tokens.contains(
+            "\" Embedded from `images/logo.png` with FNV-1a hash `0xaf63dc4c8601ec8c`.\""
+        )
+    );
+}
@@ -0,0 +1,48 @@
+//! Tests for recording actions in a session.
+
+mod common;
+
+use ts_cli_helper::{Action, Session, State};
+
+use common::capture;
+
+#[test]
+fn records_completed_actions() {
+    let capture = capture();
+
+    let session = Session::start();
+    Action::new("Building", "Built", "a", 0).set_state(State::Success);
+    Action::new("Building", "Built", "b", 0).set_error_message("missing file");
+
+    let records = session.records();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1].error.as_deref(), Some("missing file"));
+
+    let summary = session.finish();
+    assert_eq!((summary.successes, summary.errors), (1, 1));
+    assert!(
+        capture
+            .stderr()
+            .contains("error: Building b failed: missing file\n")
+    );
+}
+
+#[test]
+fn replaced_session_does_not_end_active_session() {
+    let capture = capture();
+
+    let first = Session::start();
+    let second = Session::start();
+    assert!(!first.is_active());
+    assert!(second.is_active());
+
+    drop(first);
+    Action::new("Building", "Built", "crate", 0).set_state(State::Success);
+
+    assert_eq!(second.records().len(), 1);
+    assert!(!capture.stdout().contains("actions completed"));
+
+    let summary = second.finish();
+    assert_eq!(summary.successes, 1);
+    assert!(capture.stdout().contains("Success: 1 actions completed in"));
+}
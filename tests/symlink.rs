@@ -0,0 +1,115 @@
+//! Tests for handling symlinks with each symlink policy.
+
+#![cfg(unix)]
+
+mod common;
+
+use std::{convert::Infallible, fs, os::unix::fs::symlink, path::Path};
+
+use ts_cli_helper::{FileParser, ParseFileError, ParseFileErrorKind, ParseOptions, SymlinkPolicy};
+
+use common::{TempDir, Text, names};
+
+fn parse(
+    source: &Path,
+    policy: SymlinkPolicy,
+) -> Result<FileParser<(), Text>, ParseFileError<Infallible>> {
+    let options = ParseOptions::default().with_symlink_policy(policy);
+    FileParser::parse_with(source, &mut (), &options)
+}
+
+/// Create a `root` directory with `a.txt`, and `b.txt` linking to a file outside the root.
+fn linked_dir(name: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    fs::create_dir(dir.join("root")).unwrap();
+    fs::write(dir.join("root").join("a.txt"), "a").unwrap();
+    fs::write(dir.join("target.txt"), "b").unwrap();
+    symlink(dir.join("target.txt"), dir.join("root").join("b.txt")).unwrap();
+    dir
+}
+
+#[test]
+fn follow_symlinks() {
+    let dir = linked_dir("symlink-follow");
+
+    let parser = parse(&dir.join("root"), SymlinkPolicy::Follow).unwrap();
+    assert_eq!(names(&parser), ["a", "b"]);
+    assert_eq!(parser.modules[1].contents.0, "b");
+
+    symlink(dir.join("root"), dir.join("linked-root")).unwrap();
+    let parser = parse(&dir.join("linked-root"), SymlinkPolicy::Follow).unwrap();
+    assert_eq!(names(&parser), ["a", "b"]);
+}
+
+#[test]
+fn skip_symlinks() {
+    let dir = linked_dir("symlink-skip");
+
+    let parser = parse(&dir.join("root"), SymlinkPolicy::Skip).unwrap();
+    assert_eq!(names(&parser), ["a"]);
+
+    symlink(dir.join("root"), dir.join("linked-root")).unwrap();
+    let parser = parse(&dir.join("linked-root"), SymlinkPolicy::Skip).unwrap();
+    assert!(parser.modules.is_empty());
+}
+
+#[test]
+fn error_on_symlinks() {
+    let dir = linked_dir("symlink-error");
+
+    let error = parse(&dir.join("root"), SymlinkPolicy::Error)
+        .err()
+        .unwrap();
+    assert!(matches!(
+        error.kind,
+        ParseFileErrorKind::Symlink { path, .. } if path == dir.join("root").join("b.txt")
+    ));
+}
+
+#[test]
+fn symlink_loop() {
+    let dir = TempDir::new("symlink-loop");
+    symlink("b", dir.join("a")).unwrap();
+    symlink("a", dir.join("b")).unwrap();
+
+    let error = parse(dir.path(), SymlinkPolicy::Follow).err().unwrap();
+    assert!(matches!(error.kind, ParseFileErrorKind::SymlinkLoop { .. }));
+
+    let error = parse(&dir.join("a"), SymlinkPolicy::Follow).err().unwrap();
+    assert!(matches!(error.kind, ParseFileErrorKind::SymlinkLoop { .. }));
+}
+
+#[test]
+fn broken_symlink() {
+    let dir = TempDir::new("broken-symlink");
+    symlink("missing.txt", dir.join("a.txt")).unwrap();
+
+    let error = parse(dir.path(), SymlinkPolicy::Follow).err().unwrap();
+    assert!(matches!(
+        error.kind,
+        ParseFileErrorKind::ReadSourceMetadata { .. }
+    ));
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn asset_symlink_policy() {
+    use ts_cli_helper::Assets;
+
+    let dir = linked_dir("asset-symlinks");
+    let parse = |policy| {
+        Assets::parse_with(
+            &dir.join("root"),
+            &ParseOptions::default().with_symlink_policy(policy),
+        )
+    };
+
+    let assets = parse(SymlinkPolicy::Skip).unwrap();
+    let names: Vec<_> = assets.assets.iter().map(|asset| &asset.name).collect();
+    assert_eq!(names, ["a_txt"]);
+
+    let error = parse(SymlinkPolicy::Error).err().unwrap();
+    assert!(matches!(error.kind, ParseFileErrorKind::Symlink { .. }));
+
+    assert_eq!(parse(SymlinkPolicy::Follow).unwrap().assets.len(), 2);
+}